@@ -0,0 +1,55 @@
+#ifndef MIMOSA_H
+#define MIMOSA_H
+
+/* Generated with cbindgen, see cbindgen.toml. Do not edit. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Reads the secret of the given store.
+//
+// On success, `*secret` points to a buffer of `*len` bytes, which
+// must be released with [`mimosa_free_secret`]. The buffer is not
+// NUL-terminated.
+//
+// # Safety
+//
+// `config` must be `NULL` or a valid C string, `store` must be a
+// valid C string, `secret` and `len` must be valid pointers.
+int mimosa_read(const char *config, const char *store, uint8_t **secret, size_t *len);
+
+// Writes `len` bytes of `secret` to the given store.
+//
+// # Safety
+//
+// `config` must be `NULL` or a valid C string, `store` must be a
+// valid C string, `secret` must point to `len` readable bytes.
+int mimosa_write(const char *config, const char *store, const uint8_t *secret, size_t len);
+
+// Removes the secret of the given store.
+//
+// Returns the `not-found` exit code when there was no secret.
+//
+// # Safety
+//
+// `config` must be `NULL` or a valid C string, `store` must be a
+// valid C string.
+int mimosa_remove(const char *config, const char *store);
+
+// Zeroizes and releases a secret returned by [`mimosa_read`].
+//
+// # Safety
+//
+// `secret` must be `NULL` or a buffer returned by [`mimosa_read`]
+// with its `len`, not released yet.
+void mimosa_free_secret(uint8_t *secret, size_t len);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* MIMOSA_H */
@@ -0,0 +1,46 @@
+#!/bin/sh
+
+set -eu
+
+die() {
+    printf '%s\n' "$1" >&2
+    exit "${2-1}"
+}
+
+DESTDIR="${DESTDIR:-}"
+PREFIX="${PREFIX:-/usr/local}"
+FEATURES="${FEATURES:-ffi}"
+
+cd "$(dirname "$0")"
+
+version=$(sed -n 's/^version = "\(.*\)"$/\1/p' Cargo.toml | head -n 1)
+[ -n "$version" ] || die "Cannot find crate version in Cargo.toml"
+
+tmpdir=$(mktemp -d) || die "Cannot create temporary directory"
+trap "rm -rf $tmpdir" EXIT
+
+echo "Building C library…"
+cargo rustc --release --lib --crate-type cdylib,staticlib --features "$FEATURES" \
+      -- --print native-static-libs 2>"$tmpdir/build.log" \
+    || { cat "$tmpdir/build.log" >&2; die "Cannot build C library"; }
+
+# system libraries the static library depends on, like dbus
+libs=$(sed -n 's/.*native-static-libs: //p' "$tmpdir/build.log" | tail -n 1)
+
+echo "Installing C library under $PREFIX…"
+mkdir -p "$DESTDIR$PREFIX/lib/pkgconfig" "$DESTDIR$PREFIX/include"
+
+for lib in libmimosa.so libmimosa.dylib libmimosa.a; do
+    if [ -f "target/release/$lib" ]; then
+	cp -f -- "target/release/$lib" "$DESTDIR$PREFIX/lib/$lib"
+    fi
+done
+
+cp -f -- include/mimosa.h "$DESTDIR$PREFIX/include/mimosa.h"
+
+sed -e "s|@PREFIX@|$PREFIX|" \
+    -e "s|@VERSION@|$version|" \
+    -e "s|@LIBS_PRIVATE@|$libs|" \
+    mimosa.pc.in > "$DESTDIR$PREFIX/lib/pkgconfig/mimosa.pc"
+
+die "mimosa $version C library installed!" 0
@@ -0,0 +1,85 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use std::{
+    io::{BufRead, BufReader, ErrorKind, Write},
+    os::unix::net::UnixStream,
+    path::Path,
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use log::debug;
+use zeroize::Zeroizing;
+
+use crate::agent::{
+    protocol::{Request, Response},
+    socket,
+};
+
+/// How long to wait for the agent, which may need to unlock the
+/// backend on a cache miss.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Sends the given request to the agent.
+///
+/// Returns `None` if no agent is listening at the given socket. The
+/// agent must run as the current user, behind a private directory.
+pub fn send(path: &Path, request: &Request) -> Result<Option<Response>> {
+    let mut stream = match UnixStream::connect(path) {
+        Ok(stream) => stream,
+        Err(err)
+            if matches!(
+                err.kind(),
+                ErrorKind::NotFound | ErrorKind::ConnectionRefused
+            ) =>
+        {
+            debug!("no agent listening at {}: {err}", path.display());
+            return Ok(None);
+        }
+        Err(err) => {
+            return Err(err)
+                .with_context(|| format!("Cannot connect to agent at {}", path.display()))
+        }
+    };
+
+    if let Some(dir) = path.parent() {
+        socket::check_dir(dir)?;
+    }
+
+    socket::check_peer(&stream)?;
+
+    stream
+        .set_read_timeout(Some(TIMEOUT))
+        .and_then(|()| stream.set_write_timeout(Some(TIMEOUT)))
+        .context("Cannot set agent timeout")?;
+
+    let mut request = serde_json::to_string(request).context("Cannot serialize agent request")?;
+    request.push('\n');
+    stream
+        .write_all(request.as_bytes())
+        .context("Cannot send agent request")?;
+
+    let mut line = Zeroizing::new(String::new());
+    BufReader::new(&stream)
+        .read_line(&mut line)
+        .context("Cannot receive agent response")?;
+
+    let response = serde_json::from_str(&line).context("Cannot parse agent response")?;
+    Ok(Some(response))
+}
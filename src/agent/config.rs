@@ -0,0 +1,70 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use std::{env, path::PathBuf, time::Duration};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::duration;
+
+/// The caching agent configuration.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct AgentConfig {
+    /// How long secrets are cached, defaults to 10 minutes.
+    pub ttl: Option<String>,
+    /// The agent socket path, defaults to
+    /// `$XDG_RUNTIME_DIR/mimosa/agent.sock`.
+    ///
+    /// Its directory must be owned by the current user with mode
+    /// 700, otherwise the agent is not used.
+    pub socket: Option<PathBuf>,
+    /// Prevent `password read` from asking the agent first.
+    #[serde(default)]
+    pub disable: bool,
+}
+
+impl AgentConfig {
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(600);
+
+    pub fn ttl(&self) -> Result<Duration> {
+        match &self.ttl {
+            Some(ttl) => duration::parse(ttl).context("Invalid agent `ttl`"),
+            None => Ok(Self::DEFAULT_TTL),
+        }
+    }
+
+    pub fn socket(&self) -> PathBuf {
+        if let Some(socket) = &self.socket {
+            return socket.clone();
+        }
+
+        let project = env!("CARGO_PKG_NAME");
+
+        let dir = match dirs::runtime_dir() {
+            Some(dir) => dir.join(project),
+            None => {
+                let user = env::var("USER").unwrap_or_default();
+                env::temp_dir().join(format!("{project}-{user}"))
+            }
+        };
+
+        dir.join("agent.sock")
+    }
+}
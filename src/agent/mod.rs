@@ -0,0 +1,178 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+#[cfg(unix)]
+mod client;
+mod config;
+#[cfg(unix)]
+mod protocol;
+#[cfg(unix)]
+mod server;
+#[cfg(unix)]
+mod socket;
+
+use std::{fmt, path::PathBuf, time::Duration};
+
+#[allow(unused)]
+use anyhow::{anyhow, bail, Context, Result};
+use clap::Parser;
+#[allow(unused)]
+use log::{debug, warn};
+use pimalaya_toolbox::terminal::{clap::parsers::path_parser, printer::Printer};
+use secrecy::SecretSlice;
+use serde::Serialize;
+
+use crate::{config::Config, duration, store::Store};
+
+#[doc(inline)]
+pub use self::config::*;
+
+/// Start the caching agent.
+///
+/// The agent listens on a per-user Unix socket and caches secrets in
+/// locked memory, so that backends are not queried (and unlocked)
+/// for every read. `password read` transparently asks the agent
+/// first when it is running.
+#[derive(Parser, Debug)]
+pub struct AgentCommand {
+    /// How long secrets are cached.
+    #[arg(long, short, value_name = "DURATION", value_parser = duration::parser)]
+    pub ttl: Option<Duration>,
+
+    /// Path to the agent socket.
+    #[arg(long, short, value_name = "PATH", value_parser = path_parser)]
+    pub socket: Option<PathBuf>,
+
+    /// Clear the cache of the running agent, then exit.
+    #[arg(long, conflicts_with = "ttl")]
+    pub clear: bool,
+}
+
+impl AgentCommand {
+    #[cfg(unix)]
+    pub fn execute(self, printer: &mut impl Printer, config: &Config) -> Result<()> {
+        let socket = self.socket.unwrap_or_else(|| config.agent.socket());
+
+        if self.clear {
+            let n = match client::send(&socket, &protocol::Request::Clear)? {
+                Some(protocol::Response::Cleared(n)) => n,
+                Some(protocol::Response::Error(err)) => bail!("Cannot clear agent cache: {err}"),
+                Some(response) => bail!("Unexpected agent response {response:?}"),
+                None => bail!("No agent running at {}", socket.display()),
+            };
+
+            return printer.out(AgentCleared { cleared: n });
+        }
+
+        let ttl = match self.ttl {
+            Some(ttl) => ttl,
+            None => config.agent.ttl()?,
+        };
+
+        server::run(&socket, ttl)
+    }
+
+    #[cfg(not(unix))]
+    pub fn execute(self, _printer: &mut impl Printer, _config: &Config) -> Result<()> {
+        bail!("The agent is not available on this platform");
+    }
+}
+
+/// Reads the secret of the given store through the agent.
+///
+/// Returns `None` if the agent is disabled, not running or cannot
+/// answer, in which case the store should be read directly.
+#[cfg(unix)]
+pub fn read(config: &AgentConfig, store: &Store) -> Result<Option<SecretSlice<u8>>> {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use zeroize::Zeroizing;
+
+    use crate::error::{Error, ErrorKind};
+
+    if config.disable {
+        return Ok(None);
+    }
+
+    let request = protocol::Request::Read(store.clone());
+
+    // the agent is a cache: when it cannot answer, the store is read
+    // directly
+    let response = match client::send(&config.socket(), &request) {
+        Ok(response) => response,
+        Err(err) => {
+            warn!("cannot read secret through agent: {err:#}");
+            return Ok(None);
+        }
+    };
+
+    match response {
+        None => Ok(None),
+        Some(protocol::Response::Secret(secret)) => {
+            let secret = Zeroizing::new(secret);
+            let secret = STANDARD
+                .decode(secret.as_bytes())
+                .context("Cannot decode secret from agent")?;
+            debug!("secret read through agent");
+            Ok(Some(SecretSlice::from(secret)))
+        }
+        Some(protocol::Response::NotFound) => {
+            let err = Error::new(ErrorKind::NotFound, "Secret not found in keyring");
+            Err(anyhow!(err).context("Agent error"))
+        }
+        Some(protocol::Response::Error(err)) => {
+            warn!("cannot read secret through agent: {err}");
+            Ok(None)
+        }
+        Some(response) => {
+            warn!("unexpected agent response {response:?}");
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub fn read(_config: &AgentConfig, _store: &Store) -> Result<Option<SecretSlice<u8>>> {
+    Ok(None)
+}
+
+/// Removes the given store from the agent cache, if running.
+///
+/// Failures are logged rather than returned, since they should not
+/// prevent the store from being updated.
+#[cfg(unix)]
+pub fn forget(config: &AgentConfig, store: &Store) {
+    let request = protocol::Request::Forget(store.clone());
+
+    if let Err(err) = client::send(&config.socket(), &request) {
+        warn!("cannot remove secret from agent cache: {err:#}");
+    }
+}
+
+#[cfg(not(unix))]
+pub fn forget(_config: &AgentConfig, _store: &Store) {}
+
+#[derive(Serialize)]
+struct AgentCleared {
+    cleared: usize,
+}
+
+impl fmt::Display for AgentCleared {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} secret(s) removed from agent cache", self.cleared)
+    }
+}
@@ -0,0 +1,60 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::store::Store;
+
+/// A request sent to the agent, as a single JSON line.
+///
+/// Stores are sent with their full definition, so that the agent
+/// does not depend on the configuration of the client.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Request {
+    Read(Store),
+    Forget(Store),
+    Clear,
+}
+
+/// A response sent by the agent, as a single JSON line.
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Response {
+    /// The base64-encoded secret.
+    Secret(String),
+    Forgotten(bool),
+    Cleared(usize),
+    /// The store has no secret.
+    NotFound,
+    Error(String),
+}
+
+impl fmt::Debug for Response {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Secret(_) => f.write_str("Secret([REDACTED])"),
+            Self::Forgotten(forgotten) => f.debug_tuple("Forgotten").field(forgotten).finish(),
+            Self::Cleared(n) => f.debug_tuple("Cleared").field(n).finish(),
+            Self::NotFound => f.write_str("NotFound"),
+            Self::Error(err) => f.debug_tuple("Error").field(err).finish(),
+        }
+    }
+}
@@ -0,0 +1,184 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::HashMap,
+    fs,
+    io::{BufRead, BufReader, Write},
+    os::unix::{
+        fs::PermissionsExt,
+        net::{UnixListener, UnixStream},
+    },
+    path::Path,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use log::{debug, info, warn};
+use secrecy::ExposeSecret;
+use zeroize::Zeroizing;
+
+use crate::{
+    agent::{
+        protocol::{Request, Response},
+        socket,
+    },
+    error::ErrorKind,
+    memory::LockedSecret,
+    store::{Store, StoreExt},
+};
+
+type Cache = Arc<Mutex<HashMap<String, (LockedSecret, Instant)>>>;
+
+/// Listens on the given socket, caching secrets for the given time
+/// to live.
+pub fn run(socket: &Path, ttl: Duration) -> Result<()> {
+    if let Some(dir) = socket.parent() {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Cannot create agent directory at {}", dir.display()))?;
+        fs::set_permissions(dir, fs::Permissions::from_mode(0o700))
+            .with_context(|| format!("Cannot restrict agent directory at {}", dir.display()))?;
+        socket::check_dir(dir)?;
+    }
+
+    if socket.exists() {
+        if UnixStream::connect(socket).is_ok() {
+            bail!("Agent already running at {}", socket.display());
+        }
+
+        debug!("remove stale agent socket at {}", socket.display());
+        fs::remove_file(socket).context("Cannot remove stale agent socket")?;
+    }
+
+    let listener = UnixListener::bind(socket)
+        .with_context(|| format!("Cannot bind agent socket at {}", socket.display()))?;
+    fs::set_permissions(socket, fs::Permissions::from_mode(0o600))
+        .context("Cannot restrict agent socket")?;
+
+    info!("agent listening at {}", socket.display());
+
+    let cache = Cache::default();
+
+    thread::spawn({
+        let cache = cache.clone();
+        move || loop {
+            thread::sleep(Duration::from_secs(1));
+            let mut cache = cache.lock().expect("agent cache should not be poisoned");
+            cache.retain(|_, (_, expires_at)| *expires_at > Instant::now());
+        }
+    });
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                warn!("cannot accept agent connection: {err}");
+                continue;
+            }
+        };
+
+        let cache = cache.clone();
+
+        thread::spawn(move || {
+            if let Err(err) = handle(stream, &cache, ttl) {
+                warn!("cannot handle agent connection: {err:#}");
+            }
+        });
+    }
+
+    Ok(())
+}
+
+fn handle(stream: UnixStream, cache: &Cache, ttl: Duration) -> Result<()> {
+    socket::check_peer(&stream)?;
+
+    let mut reader = BufReader::new(&stream);
+    let mut line = Zeroizing::new(String::new());
+    reader.read_line(&mut line)?;
+
+    let response = match serde_json::from_str(&line) {
+        Ok(request) => process(request, cache, ttl),
+        Err(err) => Response::Error(format!("Invalid agent request: {err}")),
+    };
+
+    let mut response = Zeroizing::new(serde_json::to_string(&response)?);
+    response.push('\n');
+    (&stream).write_all(response.as_bytes())?;
+
+    Ok(())
+}
+
+fn process(request: Request, cache: &Cache, ttl: Duration) -> Response {
+    match request {
+        Request::Read(store) => match read(&store, cache, ttl) {
+            Ok(secret) => Response::Secret(secret),
+            Err(err) if ErrorKind::of(&err) == ErrorKind::NotFound => Response::NotFound,
+            Err(err) => Response::Error(format!("{err:#}")),
+        },
+        Request::Forget(store) => match key(&store) {
+            Ok(key) => {
+                let mut cache = cache.lock().expect("agent cache should not be poisoned");
+                Response::Forgotten(cache.remove(&key).is_some())
+            }
+            Err(err) => Response::Error(format!("{err:#}")),
+        },
+        Request::Clear => {
+            let mut cache = cache.lock().expect("agent cache should not be poisoned");
+            let n = cache.len();
+            cache.clear();
+            Response::Cleared(n)
+        }
+    }
+}
+
+fn read(store: &Store, cache: &Cache, ttl: Duration) -> Result<String> {
+    let key = key(store)?;
+
+    {
+        let cache = cache.lock().expect("agent cache should not be poisoned");
+
+        if let Some((secret, expires_at)) = cache.get(&key) {
+            if *expires_at > Instant::now() {
+                debug!("agent cache hit");
+                return Ok(STANDARD.encode(secret.expose_secret()));
+            }
+        }
+    }
+
+    debug!("agent cache miss, reading from store");
+
+    let Some(expires_at) = Instant::now().checked_add(ttl) else {
+        bail!("Invalid agent ttl, too large");
+    };
+
+    let secret = store.read_secret()?;
+    let encoded = STANDARD.encode(secret.expose_secret());
+    let secret = LockedSecret::new(secret.expose_secret());
+
+    let mut cache = cache.lock().expect("agent cache should not be poisoned");
+    cache.insert(key, (secret, expires_at));
+
+    Ok(encoded)
+}
+
+fn key(store: &Store) -> Result<String> {
+    serde_json::to_string(store).context("Cannot serialize store")
+}
@@ -0,0 +1,154 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use std::{
+    fs, io,
+    os::unix::{fs::MetadataExt, net::UnixStream},
+    path::Path,
+};
+
+use anyhow::{bail, Context, Result};
+
+/// Ensures the given agent directory can only be used by the current
+/// user.
+///
+/// The directory may live in a shared place like `/tmp`, where
+/// another user could create it first and serve forged secrets.
+pub fn check_dir(dir: &Path) -> Result<()> {
+    let metadata = fs::symlink_metadata(dir)
+        .with_context(|| format!("Cannot read agent directory at {}", dir.display()))?;
+
+    if metadata.uid() != uid() {
+        bail!(
+            "Agent directory at {} is owned by another user",
+            dir.display()
+        );
+    }
+
+    if !metadata.is_dir() || metadata.mode() & 0o777 != 0o700 {
+        let mode = metadata.mode() & 0o777;
+        bail!(
+            "Agent directory at {} has mode {mode:o}, expected 700",
+            dir.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// Ensures the process at the other end of the given stream runs as
+/// the current user.
+pub fn check_peer(stream: &UnixStream) -> Result<()> {
+    let peer = peer_uid(stream).context("Cannot get agent peer credentials")?;
+
+    if peer != uid() {
+        bail!("Agent peer runs as user {peer}, expected {}", uid());
+    }
+
+    Ok(())
+}
+
+fn uid() -> libc::uid_t {
+    // SAFETY: geteuid always succeeds
+    unsafe { libc::geteuid() }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn peer_uid(stream: &UnixStream) -> io::Result<libc::uid_t> {
+    use std::{mem, os::fd::AsRawFd};
+
+    let mut cred = libc::ucred {
+        pid: 0,
+        uid: 0,
+        gid: 0,
+    };
+    let mut len = mem::size_of::<libc::ucred>() as libc::socklen_t;
+
+    // SAFETY: the descriptor is open for the lifetime of the stream,
+    // and the buffer and its length describe a valid ucred
+    let res = unsafe {
+        libc::getsockopt(
+            stream.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_PEERCRED,
+            (&mut cred as *mut libc::ucred).cast(),
+            &mut len,
+        )
+    };
+
+    if res != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(cred.uid)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn peer_uid(stream: &UnixStream) -> io::Result<libc::uid_t> {
+    use std::os::fd::AsRawFd;
+
+    let mut uid = 0;
+    let mut gid = 0;
+
+    // SAFETY: the descriptor is open for the lifetime of the stream
+    if unsafe { libc::getpeereid(stream.as_raw_fd(), &mut uid, &mut gid) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(uid)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::{self, Permissions},
+        os::unix::{fs::PermissionsExt, net::UnixStream},
+    };
+
+    use super::{check_dir, check_peer};
+
+    #[test]
+    fn private_dir() {
+        let dir = tempfile::tempdir().unwrap();
+
+        fs::set_permissions(dir.path(), Permissions::from_mode(0o700)).unwrap();
+        assert!(check_dir(dir.path()).is_ok());
+
+        fs::set_permissions(dir.path(), Permissions::from_mode(0o755)).unwrap();
+        assert!(check_dir(dir.path()).is_err());
+
+        assert!(check_dir(&dir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn symlinked_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let link = dir.path().join("link");
+
+        fs::set_permissions(dir.path(), Permissions::from_mode(0o700)).unwrap();
+        std::os::unix::fs::symlink(dir.path(), &link).unwrap();
+
+        assert!(check_dir(&link).is_err());
+    }
+
+    #[test]
+    fn same_user_peer() {
+        let (stream, _) = UnixStream::pair().unwrap();
+        assert!(check_peer(&stream).is_ok());
+    }
+}
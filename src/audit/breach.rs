@@ -0,0 +1,237 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use std::{
+    cmp::Ordering,
+    fs::File,
+    io::{BufRead, BufReader, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+use log::debug;
+use sha1::{Digest, Sha1};
+
+/// A local database of breached password hashes.
+///
+/// It can be either a single file of sorted SHA-1 hashes (like the
+/// Have I Been Pwned "ordered by hash" download), or a directory of
+/// range files named after the first 5 characters of the hashes they
+/// contain (like the Have I Been Pwned range API responses). Lines
+/// can be suffixed by `:<count>`.
+#[derive(Clone, Debug)]
+pub struct Breaches {
+    path: PathBuf,
+}
+
+impl Breaches {
+    pub fn new(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+
+        if !path.exists() {
+            bail!("Cannot find breached hashes at {}", path.display());
+        }
+
+        Ok(Self { path })
+    }
+
+    /// Looks for the given password, returning the number of times
+    /// it appeared in breaches.
+    ///
+    /// Hash lists without counts report a single occurrence.
+    pub fn find(&self, password: &[u8]) -> Result<Option<u64>> {
+        let hash = hex::encode_upper(Sha1::digest(password));
+
+        if !self.path.is_dir() {
+            return search(&self.path, &hash);
+        }
+
+        let (prefix, suffix) = hash.split_at(5);
+
+        for name in [prefix.to_owned(), format!("{prefix}.txt")] {
+            let path = self.path.join(name);
+
+            if path.is_file() {
+                return search(&path, suffix);
+            }
+        }
+
+        debug!("no range file found for prefix {prefix}");
+        Ok(None)
+    }
+}
+
+/// Binary searches the given key in the sorted file, without loading
+/// it entirely in memory.
+fn search(path: &Path, key: &str) -> Result<Option<u64>> {
+    let file = File::open(path).with_context(|| format!("Cannot open {}", path.display()))?;
+    let len = file.metadata()?.len();
+    let mut reader = BufReader::new(file);
+    let mut line = String::new();
+
+    let mut lo = 0;
+    let mut hi = len;
+
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+
+        // move to the beginning of the first line starting at or
+        // after the middle
+        let mut start = mid;
+
+        if mid > 0 {
+            reader.seek(SeekFrom::Start(mid - 1))?;
+            line.clear();
+            start = mid - 1 + reader.read_line(&mut line)? as u64;
+        } else {
+            reader.seek(SeekFrom::Start(0))?;
+        }
+
+        // skip blank lines, which do not follow the hashes order
+        let n = loop {
+            line.clear();
+            let n = reader.read_line(&mut line)? as u64;
+
+            if n == 0 || !line.trim().is_empty() {
+                break n;
+            }
+
+            start += n;
+        };
+
+        if start >= hi {
+            hi = mid;
+            continue;
+        }
+
+        let (entry, count) = parse_line(&line);
+
+        match entry.to_ascii_uppercase().as_str().cmp(key) {
+            Ordering::Equal => return Ok(Some(count)),
+            Ordering::Less => lo = start + n,
+            Ordering::Greater => hi = mid,
+        }
+    }
+
+    Ok(None)
+}
+
+fn parse_line(line: &str) -> (&str, u64) {
+    let line = line.trim();
+
+    match line.split_once(':') {
+        Some((hash, count)) => (hash.trim_end(), count.trim().parse().unwrap_or(1)),
+        None => (line, 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use sha1::{Digest, Sha1};
+    use tempfile::TempDir;
+
+    use super::search;
+
+    fn hashes() -> Vec<String> {
+        let mut hashes: Vec<String> = (0..100)
+            .map(|n| hex::encode_upper(Sha1::digest(n.to_string())))
+            .collect();
+
+        hashes.sort();
+        hashes
+    }
+
+    fn write(name: &str, contents: &str) -> (TempDir, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(name);
+        fs::write(&path, contents).unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn found() {
+        let hashes = hashes();
+        let contents: String = hashes
+            .iter()
+            .enumerate()
+            .map(|(i, hash)| format!("{hash}:{}\n", i + 1))
+            .collect();
+
+        let (_dir, path) = write("found.txt", &contents);
+
+        let first = &hashes[0];
+        assert_eq!(search(&path, first).unwrap(), Some(1));
+
+        let last = &hashes[hashes.len() - 1];
+        assert_eq!(search(&path, last).unwrap(), Some(hashes.len() as u64));
+
+        for (i, hash) in hashes.iter().enumerate() {
+            assert_eq!(search(&path, hash).unwrap(), Some(i as u64 + 1), "{hash}");
+        }
+    }
+
+    #[test]
+    fn missing() {
+        let hashes = hashes();
+        let contents: String = hashes.iter().step_by(2).map(|h| format!("{h}\n")).collect();
+        let (_dir, path) = write("missing.txt", &contents);
+
+        for hash in hashes.iter().skip(1).step_by(2) {
+            assert_eq!(search(&path, hash).unwrap(), None, "{hash}");
+        }
+
+        assert_eq!(search(&path, &"0".repeat(40)).unwrap(), None);
+        assert_eq!(search(&path, &"F".repeat(40)).unwrap(), None);
+
+        let (_dir, path) = write("empty.txt", "");
+        assert_eq!(search(&path, &hashes[0]).unwrap(), None);
+    }
+
+    #[test]
+    fn malformed() {
+        let hashes = hashes();
+        let lines = [
+            // no count
+            hashes[0].clone(),
+            // invalid count
+            format!("{}:many", hashes[1]),
+            // lowercase hash
+            format!("{}:3", hashes[2].to_lowercase()),
+            // CRLF line ending and surrounding whitespace
+            format!(" {} : 4 \r", hashes[3]),
+            String::new(),
+            format!("{}:5", hashes[4]),
+        ];
+
+        // blank lines in the middle and at the end
+        let (_dir, path) = write("malformed.txt", &format!("{}\n\n", lines.join("\n")));
+
+        assert_eq!(search(&path, &hashes[0]).unwrap(), Some(1));
+        assert_eq!(search(&path, &hashes[1]).unwrap(), Some(1));
+        assert_eq!(search(&path, &hashes[2]).unwrap(), Some(3));
+        assert_eq!(search(&path, &hashes[3]).unwrap(), Some(4));
+        assert_eq!(search(&path, &hashes[4]).unwrap(), Some(5));
+        assert_eq!(search(&path, &hashes[5]).unwrap(), None);
+
+        // no final line ending
+        let (_dir, path) = write("unterminated.txt", &lines.join("\n"));
+        assert_eq!(search(&path, &hashes[4]).unwrap(), Some(5));
+    }
+}
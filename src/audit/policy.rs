@@ -0,0 +1,100 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+
+use crate::audit::Strength;
+
+/// The password policy of a store.
+///
+/// Every rule is optional, only the configured ones are checked by
+/// `mimosa audit`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct Policy {
+    /// The minimum number of characters.
+    pub min_length: Option<usize>,
+    /// The maximum number of characters.
+    pub max_length: Option<usize>,
+    /// The minimum estimated entropy, in bits.
+    pub min_entropy: Option<f64>,
+    /// Require at least one lowercase letter.
+    #[serde(default)]
+    pub require_lowercase: bool,
+    /// Require at least one uppercase letter.
+    #[serde(default)]
+    pub require_uppercase: bool,
+    /// Require at least one digit.
+    #[serde(default)]
+    pub require_digits: bool,
+    /// Require at least one symbol.
+    #[serde(default)]
+    pub require_symbols: bool,
+    /// Forbid passwords containing a dictionary word.
+    #[serde(default)]
+    pub forbid_dictionary: bool,
+}
+
+impl Policy {
+    /// Checks the given password strength against the policy,
+    /// returning the list of violations.
+    pub fn check(&self, strength: &Strength) -> Vec<String> {
+        let mut violations = Vec::new();
+
+        if let Some(min) = self.min_length {
+            if strength.length < min {
+                violations.push(format!("policy requires at least {min} characters"));
+            }
+        }
+
+        if let Some(max) = self.max_length {
+            if strength.length > max {
+                violations.push(format!("policy allows at most {max} characters"));
+            }
+        }
+
+        if let Some(min) = self.min_entropy {
+            if strength.entropy < min {
+                violations.push(format!("policy requires at least {min} bits of entropy"));
+            }
+        }
+
+        if self.require_lowercase && !strength.lowercase {
+            violations.push("policy requires a lowercase letter".into());
+        }
+
+        if self.require_uppercase && !strength.uppercase {
+            violations.push("policy requires an uppercase letter".into());
+        }
+
+        if self.require_digits && !strength.digits {
+            violations.push("policy requires a digit".into());
+        }
+
+        if self.require_symbols && !strength.symbols {
+            violations.push("policy requires a symbol".into());
+        }
+
+        if self.forbid_dictionary && strength.dictionary {
+            violations.push("policy forbids dictionary words".into());
+        }
+
+        violations
+    }
+}
@@ -0,0 +1,282 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+/// Passwords shorter than this are considered weak.
+pub const WEAK_LENGTH: usize = 8;
+
+/// Passwords with an estimated entropy lower than this, in bits, are
+/// considered weak.
+pub const WEAK_ENTROPY: f64 = 50.0;
+
+/// Words shorter than this are ignored when looking for dictionary
+/// matches, to avoid false positives.
+const MIN_WORD_LENGTH: usize = 4;
+
+/// Most common passwords and password fragments.
+const COMMON_WORDS: &[&str] = &[
+    "password",
+    "passw0rd",
+    "123456",
+    "12345678",
+    "123456789",
+    "1234567890",
+    "qwerty",
+    "qwertz",
+    "azerty",
+    "abc123",
+    "111111",
+    "000000",
+    "letmein",
+    "welcome",
+    "monkey",
+    "dragon",
+    "master",
+    "login",
+    "admin",
+    "administrator",
+    "root",
+    "toor",
+    "princess",
+    "sunshine",
+    "shadow",
+    "superman",
+    "batman",
+    "trustno1",
+    "iloveyou",
+    "football",
+    "baseball",
+    "soccer",
+    "hockey",
+    "starwars",
+    "freedom",
+    "whatever",
+    "secret",
+    "access",
+    "hello",
+    "charlie",
+    "donald",
+    "michael",
+    "jordan",
+    "jennifer",
+    "hunter",
+    "ranger",
+    "buster",
+    "summer",
+    "winter",
+    "spring",
+    "autumn",
+    "changeme",
+    "default",
+    "guest",
+    "test",
+    "testing",
+    "asdf",
+    "asdfgh",
+    "zxcvbn",
+    "1q2w3e",
+    "q1w2e3",
+    "qazwsx",
+    "mustang",
+    "killer",
+    "pepper",
+    "cookie",
+    "cheese",
+    "computer",
+    "internet",
+    "google",
+    "samsung",
+    "apple",
+    "orange",
+    "banana",
+    "flower",
+    "purple",
+    "silver",
+    "golden",
+    "tigger",
+    "ginger",
+    "maggie",
+    "daniel",
+    "thomas",
+    "robert",
+    "matrix",
+    "ninja",
+    "pokemon",
+    "mimosa",
+];
+
+/// A list of words that should not appear in passwords.
+#[derive(Clone, Debug)]
+pub struct Dictionary(Vec<String>);
+
+impl Default for Dictionary {
+    fn default() -> Self {
+        Self(COMMON_WORDS.iter().map(ToString::to_string).collect())
+    }
+}
+
+impl Dictionary {
+    /// Extends the dictionary with the words of the given file, one
+    /// per line.
+    pub fn extend_from_file(&mut self, path: &Path) -> Result<()> {
+        let words = fs::read_to_string(path)
+            .with_context(|| format!("Cannot read dictionary at {}", path.display()))?;
+
+        let words = words
+            .lines()
+            .map(|word| word.trim().to_lowercase())
+            .filter(|word| word.chars().count() >= MIN_WORD_LENGTH);
+
+        self.0.extend(words);
+        Ok(())
+    }
+
+    /// Returns true if the password contains one of the words,
+    /// case-insensitively and after common character substitutions.
+    pub fn matches(&self, password: &str) -> bool {
+        let lowercase = password.to_lowercase();
+        let normalized = normalize(&lowercase);
+
+        self.0
+            .iter()
+            .any(|word| lowercase.contains(word.as_str()) || normalized.contains(word.as_str()))
+    }
+}
+
+/// The estimated strength of a password.
+///
+/// Only metadata are kept, the password itself is never stored.
+#[derive(Clone, Debug, Serialize)]
+pub struct Strength {
+    pub length: usize,
+    pub entropy: f64,
+    pub lowercase: bool,
+    pub uppercase: bool,
+    pub digits: bool,
+    pub symbols: bool,
+    pub dictionary: bool,
+}
+
+impl Strength {
+    pub fn new(password: &str, dictionary: &Dictionary) -> Self {
+        let length = password.chars().count();
+        let lowercase = password.chars().any(|c| c.is_lowercase());
+        let uppercase = password.chars().any(|c| c.is_uppercase());
+        let digits = password.chars().any(|c| c.is_ascii_digit());
+        let symbols = password
+            .chars()
+            .any(|c| c.is_ascii_punctuation() || c == ' ');
+        let others = password
+            .chars()
+            .any(|c| !c.is_alphanumeric() && !c.is_ascii_punctuation() && c != ' ');
+
+        let mut pool = 0;
+
+        if lowercase {
+            pool += 26;
+        }
+
+        if uppercase {
+            pool += 26;
+        }
+
+        if digits {
+            pool += 10;
+        }
+
+        if symbols {
+            pool += 33;
+        }
+
+        if others {
+            pool += 100;
+        }
+
+        let entropy = if pool == 0 {
+            0.0
+        } else {
+            (length as f64 * f64::from(pool).log2() * 10.0).round() / 10.0
+        };
+
+        Self {
+            length,
+            entropy,
+            lowercase,
+            uppercase,
+            digits,
+            symbols,
+            dictionary: dictionary.matches(password),
+        }
+    }
+
+    /// Returns the number of character classes used.
+    pub fn classes(&self) -> usize {
+        [self.lowercase, self.uppercase, self.digits, self.symbols]
+            .into_iter()
+            .filter(|class| *class)
+            .count()
+    }
+
+    /// Returns the list of weaknesses.
+    pub fn weaknesses(&self) -> Vec<String> {
+        let mut weaknesses = Vec::new();
+
+        if self.length < WEAK_LENGTH {
+            weaknesses.push(format!(
+                "too short: {} characters, expected at least {WEAK_LENGTH}",
+                self.length
+            ));
+        }
+
+        if self.entropy < WEAK_ENTROPY {
+            weaknesses.push(format!(
+                "low entropy: {:.0} bits, expected at least {WEAK_ENTROPY}",
+                self.entropy
+            ));
+        }
+
+        if self.classes() < 2 {
+            weaknesses.push("single character class".into());
+        }
+
+        if self.dictionary {
+            weaknesses.push("contains a common password or dictionary word".into());
+        }
+
+        weaknesses
+    }
+}
+
+fn normalize(password: &str) -> String {
+    password
+        .chars()
+        .map(|c| match c {
+            '0' => 'o',
+            '1' | '!' => 'i',
+            '3' => 'e',
+            '4' | '@' => 'a',
+            '5' | '$' => 's',
+            '7' => 't',
+            c => c,
+        })
+        .collect()
+}
@@ -1,74 +0,0 @@
-// This file is part of Mimosa, a CLI to manage passwords.
-//
-// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
-//
-// This program is free software: you can redistribute it and/or
-// modify it under the terms of the GNU Affero General Public License
-// as published by the Free Software Foundation, either version 3 of
-// the License, or (at your option) any later version.
-//
-// This program is distributed in the hope that it will be useful, but
-// WITHOUT ANY WARRANTY; without even the implied warranty of
-// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
-// Affero General Public License for more details.
-//
-// You should have received a copy of the GNU Affero General Public
-// License along with this program. If not, see
-// <https://www.gnu.org/licenses/>.
-
-use std::path::PathBuf;
-
-use anyhow::Result;
-use clap::{CommandFactory, Parser, Subcommand};
-use pimalaya_toolbox::{
-    config::TomlConfig,
-    long_version,
-    terminal::{
-        clap::{
-            args::{ConfigPathsArg, JsonFlag, LogFlags},
-            commands::{CompletionCommand, ManualCommand},
-        },
-        printer::Printer,
-    },
-};
-
-use crate::{config::Config, password::PasswordCommand};
-
-#[derive(Parser, Debug)]
-#[command(name = env!("CARGO_PKG_NAME"))]
-#[command(author, version, about)]
-#[command(long_version = long_version!())]
-#[command(propagate_version = true, infer_subcommands = true)]
-pub struct Cli {
-    #[command(subcommand)]
-    pub command: MimosaCommand,
-    #[command(flatten)]
-    pub config: ConfigPathsArg,
-    #[command(flatten)]
-    pub json: JsonFlag,
-    #[command(flatten)]
-    pub log: LogFlags,
-}
-
-#[derive(Subcommand, Debug)]
-pub enum MimosaCommand {
-    #[command(arg_required_else_help = true, subcommand)]
-    Password(PasswordCommand),
-    #[command(arg_required_else_help = true, alias = "mans")]
-    Manuals(ManualCommand),
-    #[command(arg_required_else_help = true)]
-    Completions(CompletionCommand),
-}
-
-impl MimosaCommand {
-    pub fn execute(self, printer: &mut impl Printer, config_paths: &[PathBuf]) -> Result<()> {
-        match self {
-            Self::Password(cmd) => {
-                let config = Config::from_paths_or_default(config_paths)?;
-                cmd.execute(printer, &config)
-            }
-            Self::Manuals(cmd) => cmd.execute(printer, Cli::command()),
-            Self::Completions(cmd) => cmd.execute(printer, Cli::command()),
-        }
-    }
-}
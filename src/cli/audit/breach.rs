@@ -0,0 +1,181 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use std::{fmt, path::PathBuf};
+
+use anyhow::Result;
+use clap::Parser;
+use log::debug;
+use pimalaya_toolbox::terminal::{clap::parsers::path_parser, printer::Printer};
+use secrecy::ExposeSecret;
+use serde::Serialize;
+
+use crate::{
+    audit::Breaches,
+    config::Config,
+    error::{Error, ErrorKind},
+    store::{EntryType, StoreExt},
+};
+
+/// Check passwords against a local database of breached hashes.
+///
+/// SHA-1 hashes of the passwords are searched in a locally downloaded
+/// Have I Been Pwned hash list or range files directory. Nothing is
+/// sent over the network. Exits with an error if at least one
+/// password is compromised.
+#[derive(Parser, Debug)]
+pub struct BreachCheckCommand {
+    /// Path to a sorted hash list, or to a directory of range files.
+    #[arg(long, short = 'H', value_name = "PATH", value_parser = path_parser)]
+    pub hashes: PathBuf,
+
+    /// Names of the stores to check, all of them when omitted.
+    pub stores: Vec<String>,
+}
+
+impl BreachCheckCommand {
+    pub fn execute(self, printer: &mut impl Printer, config: &Config) -> Result<()> {
+        let breaches = Breaches::new(self.hashes)?;
+
+        let mut names: Vec<String> = if self.stores.is_empty() {
+            config.stores.keys().cloned().collect()
+        } else {
+            self.stores
+        };
+
+        names.sort();
+
+        let mut report = BreachReport::default();
+
+        for name in names {
+            // stores that cannot be rendered are reported, so that
+            // one missing variable does not abort the whole check
+            let store = match config.get_store(&name) {
+                Ok(store) => store,
+                Err(err) if config.stores.contains_key(&name) => {
+                    let err = anyhow::Error::from(err);
+                    let err = format!("cannot render store: {}", err.root_cause());
+                    report.stores.push(BreachCheck::unreadable(name, err));
+                    continue;
+                }
+                Err(err) => return Err(err.into()),
+            };
+
+            // TOTP seeds are random keys, not passwords
+            if store.entry() == EntryType::Totp {
+                debug!("skip TOTP store {name}");
+                continue;
+            }
+
+            let check = match store.read() {
+                Ok(password) => match breaches.find(password.expose_secret().as_bytes())? {
+                    Some(occurrences) => BreachCheck::compromised(name, occurrences),
+                    None => BreachCheck::safe(name),
+                },
+                Err(err) => {
+                    let err = format!("cannot read password: {err:#}");
+                    BreachCheck::unreadable(name, err)
+                }
+            };
+
+            report.stores.push(check);
+        }
+
+        let compromised = report
+            .stores
+            .iter()
+            .filter(|check| check.compromised == Some(true))
+            .count();
+
+        printer.out(report)?;
+
+        if compromised > 0 {
+            // the report already lists the compromised passwords
+            let message = format!("{compromised} compromised password(s) found");
+            return Err(Error::new(ErrorKind::Other, message).reported().into());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+struct BreachReport {
+    stores: Vec<BreachCheck>,
+}
+
+#[derive(Debug, Serialize)]
+struct BreachCheck {
+    store: String,
+    compromised: Option<bool>,
+    occurrences: Option<u64>,
+    error: Option<String>,
+}
+
+impl BreachCheck {
+    fn compromised(store: String, occurrences: u64) -> Self {
+        Self {
+            store,
+            compromised: Some(true),
+            occurrences: Some(occurrences),
+            error: None,
+        }
+    }
+
+    fn safe(store: String) -> Self {
+        Self {
+            store,
+            compromised: Some(false),
+            occurrences: None,
+            error: None,
+        }
+    }
+
+    fn unreadable(store: String, error: String) -> Self {
+        Self {
+            store,
+            compromised: None,
+            occurrences: None,
+            error: Some(error),
+        }
+    }
+}
+
+impl fmt::Display for BreachReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut first = true;
+
+        for check in &self.stores {
+            if !first {
+                writeln!(f)?;
+            }
+
+            first = false;
+
+            let s = &check.store;
+
+            match (&check.error, check.occurrences) {
+                (Some(err), _) => write!(f, "{s}: {err}")?,
+                (None, Some(n)) => write!(f, "{s}: compromised, seen {n} time(s) in breaches")?,
+                (None, None) => write!(f, "{s}: not found in breaches")?,
+            }
+        }
+
+        Ok(())
+    }
+}
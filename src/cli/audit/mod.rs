@@ -0,0 +1,216 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+mod breach;
+
+use std::{collections::HashMap, fmt, path::PathBuf};
+
+use anyhow::Result;
+use clap::Parser;
+use log::debug;
+use pimalaya_toolbox::terminal::{clap::parsers::path_parser, printer::Printer};
+use secrecy::ExposeSecret;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    audit::{Dictionary, Strength},
+    config::Config,
+    store::{EntryType, StoreExt},
+};
+
+pub use self::breach::*;
+
+/// Audit the strength of the passwords.
+///
+/// Every store is read, then reported if its password is weak (too
+/// short, low entropy, dictionary words), reused by other stores or
+/// violates its policy. Passwords are never printed: duplicates are
+/// detected by comparing hashes.
+#[derive(Parser, Debug)]
+pub struct AuditCommand {
+    /// Names of the stores to audit, all of them when omitted.
+    pub stores: Vec<String>,
+
+    /// Extend the built-in dictionary with a words file.
+    ///
+    /// The file must contain one word per line.
+    #[arg(long, short, value_name = "PATH", value_parser = path_parser)]
+    pub dictionary: Option<PathBuf>,
+}
+
+impl AuditCommand {
+    pub fn execute(self, printer: &mut impl Printer, config: &Config) -> Result<()> {
+        let mut dictionary = Dictionary::default();
+
+        if let Some(path) = &self.dictionary {
+            dictionary.extend_from_file(path)?;
+        }
+
+        let mut names: Vec<String> = if self.stores.is_empty() {
+            config.stores.keys().cloned().collect()
+        } else {
+            self.stores
+        };
+
+        names.sort();
+
+        let mut report = AuditReport::default();
+        let mut hashes: HashMap<Vec<u8>, Vec<String>> = HashMap::new();
+
+        for name in names {
+            // stores that cannot be rendered are reported, so that
+            // one missing variable does not abort the whole audit
+            let store = match config.get_store(&name) {
+                Ok(store) => store,
+                Err(err) if config.stores.contains_key(&name) => {
+                    let err = anyhow::Error::from(err);
+                    report.stores.push(StoreAudit {
+                        store: name,
+                        strength: None,
+                        issues: vec![format!("cannot render store: {}", err.root_cause())],
+                    });
+                    continue;
+                }
+                Err(err) => return Err(err.into()),
+            };
+
+            // TOTP seeds are random keys, not passwords
+            if store.entry() == EntryType::Totp {
+                debug!("skip TOTP store {name}");
+                continue;
+            }
+
+            let password = match store.read() {
+                Ok(password) => password,
+                Err(err) => {
+                    report.stores.push(StoreAudit {
+                        store: name,
+                        strength: None,
+                        issues: vec![format!("cannot read password: {err:#}")],
+                    });
+                    continue;
+                }
+            };
+
+            let password = password.expose_secret();
+            let strength = Strength::new(password, &dictionary);
+            let mut issues = strength.weaknesses();
+
+            if let Some(policy) = &store.policy {
+                issues.extend(policy.check(&strength));
+            }
+
+            let hash = Sha256::digest(password.as_bytes()).to_vec();
+            hashes.entry(hash).or_default().push(name.clone());
+
+            report.stores.push(StoreAudit {
+                store: name,
+                strength: Some(strength),
+                issues,
+            });
+        }
+
+        report.duplicates = hashes
+            .into_values()
+            .filter(|stores| stores.len() > 1)
+            .collect();
+
+        report.duplicates.sort();
+
+        for audit in &mut report.stores {
+            let mut duplicates = report.duplicates.iter();
+            let Some(stores) = duplicates.find(|s| s.contains(&audit.store)) else {
+                continue;
+            };
+
+            let others: Vec<&str> = stores
+                .iter()
+                .filter(|s| **s != audit.store)
+                .map(String::as_str)
+                .collect();
+
+            audit
+                .issues
+                .push(format!("reused by {}", others.join(", ")));
+        }
+
+        printer.out(report)
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+struct AuditReport {
+    stores: Vec<StoreAudit>,
+    duplicates: Vec<Vec<String>>,
+}
+
+#[derive(Debug, Serialize)]
+struct StoreAudit {
+    store: String,
+    strength: Option<Strength>,
+    issues: Vec<String>,
+}
+
+impl fmt::Display for AuditReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut first = true;
+
+        for audit in &self.stores {
+            if !first {
+                writeln!(f)?;
+            }
+
+            first = false;
+
+            let s = &audit.store;
+
+            match &audit.strength {
+                Some(strength) => write!(
+                    f,
+                    "{s}: {} characters, {:.0} bits of entropy, {} character classes",
+                    strength.length,
+                    strength.entropy,
+                    strength.classes(),
+                )?,
+                None => write!(f, "{s}:")?,
+            }
+
+            if audit.issues.is_empty() {
+                write!(f, "\n - ok")?;
+            }
+
+            for issue in &audit.issues {
+                write!(f, "\n - {issue}")?;
+            }
+        }
+
+        let issues: usize = self.stores.iter().map(|audit| audit.issues.len()).sum();
+
+        if !first {
+            writeln!(f)?;
+            writeln!(f)?;
+        }
+
+        write!(
+            f,
+            "{issues} issue(s) found in {} store(s)",
+            self.stores.len()
+        )
+    }
+}
@@ -0,0 +1,592 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use clap::Parser;
+use pimalaya_toolbox::terminal::printer::Printer;
+use serde::{de::IntoDeserializer, Deserialize, Serialize};
+use toml::{Table, Value};
+use toml_edit::{ImDocument, Item, TableLike};
+
+use crate::{
+    agent::AgentConfig,
+    audit::Policy,
+    clipboard::ClipboardConfig,
+    config::{
+        schema::{fields, EXTENDS_KEY, LOADER_KEYS},
+        Config,
+    },
+    error::Error,
+    password::source::ArgvSecrets,
+    store::{
+        keyutils::KeyutilsStore, macos::MacosStore, secret_service::SecretServiceStore,
+        windows::WindowsStore, Store, StoreKind,
+    },
+};
+
+/// Check the configuration.
+///
+/// This command parses the configuration with its includes, local
+/// overlay and environment overrides, then reports every problem
+/// found with its location: unknown keys, store kinds without their
+/// backend table, backend tables not matching the store kind and
+/// backends not compiled in. It exits with an error when at least
+/// one problem is found.
+#[derive(Parser, Debug)]
+pub struct CheckConfigCommand;
+
+impl CheckConfigCommand {
+    pub fn execute(self, printer: &mut impl Printer, config_paths: &[PathBuf]) -> Result<()> {
+        let mut checker = Checker::default();
+
+        match Config::load_table(config_paths) {
+            Ok((table, files)) => {
+                for file in &files {
+                    checker.check_file(file);
+                }
+
+                checker.check_table(table);
+            }
+            Err(err) => checker.push(None, format!("{err:#}")),
+        }
+
+        let problems = checker.diagnostics.len();
+        printer.out(Report {
+            diagnostics: checker.diagnostics,
+        })?;
+
+        if problems > 0 {
+            let message = format!("{problems} problem(s) found in config");
+            return Err(Error::config(message).reported().into());
+        }
+
+        Ok(())
+    }
+}
+
+/// A position in a configuration file.
+#[derive(Clone, Debug, Serialize)]
+pub struct Location {
+    pub path: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    fn new(path: &Path, content: &str, span: Option<Range<usize>>) -> Self {
+        let offset = span.map(|span| span.start).unwrap_or_default();
+        let before = &content[..offset.min(content.len())];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or_default();
+
+        Self {
+            path: path.to_owned(),
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.path.display(), self.line, self.column)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Diagnostic {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<Location>,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "{location}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Report {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.diagnostics.is_empty() {
+            return write!(f, "Config is valid");
+        }
+
+        for (i, diagnostic) in self.diagnostics.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            write!(f, "{diagnostic}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Locations of a store definition, as found in the last file
+/// defining it.
+#[derive(Default)]
+struct StoreLocations {
+    header: Option<Location>,
+    keys: HashMap<String, Location>,
+}
+
+impl StoreLocations {
+    fn get(&self, key: &str) -> Option<Location> {
+        self.keys.get(key).or(self.header.as_ref()).cloned()
+    }
+}
+
+#[derive(Default)]
+struct Checker {
+    diagnostics: Vec<Diagnostic>,
+    stores: HashMap<String, StoreLocations>,
+}
+
+impl Checker {
+    fn push(&mut self, location: Option<Location>, message: impl ToString) {
+        self.diagnostics.push(Diagnostic {
+            location,
+            message: message.to_string(),
+        });
+    }
+
+    /// Checks the keys of a single configuration file, and records
+    /// where stores are defined.
+    fn check_file(&mut self, path: &Path) {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) => return self.push(None, format!("Cannot read {}: {err}", path.display())),
+        };
+
+        let doc = match ImDocument::parse(content.as_str()) {
+            Ok(doc) => doc,
+            Err(err) => {
+                let location = Location::new(path, &content, err.span());
+                return self.push(Some(location), err.message().trim());
+            }
+        };
+
+        let loc = |span| Location::new(path, &content, span);
+        let top = doc.as_table();
+
+        let top_keys = [fields::<Config>(), LOADER_KEYS].concat();
+        self.check_keys(top, &top_keys, "", &loc);
+
+        let tables = [
+            ("clipboard", fields::<ClipboardConfig>()),
+            ("agent", fields::<AgentConfig>()),
+        ];
+
+        for (name, keys) in tables {
+            if let Some(table) = top.get(name).and_then(Item::as_table_like) {
+                self.check_keys(table, keys, name, &loc);
+            }
+        }
+
+        if let Some(defaults) = top.get("defaults").and_then(Item::as_table_like) {
+            self.check_store(defaults, "defaults", &loc);
+
+            if let Some((key, _)) = defaults.get_key_value(EXTENDS_KEY) {
+                self.push(
+                    Some(loc(key.span())),
+                    "`extends` is not allowed in defaults",
+                );
+            }
+        }
+
+        let Some(stores) = top.get("stores").and_then(Item::as_table_like) else {
+            return;
+        };
+
+        for (name, item) in stores.iter() {
+            let Some((key, _)) = stores.get_key_value(name) else {
+                continue;
+            };
+
+            let Some(store) = item.as_table_like() else {
+                self.push(
+                    Some(loc(key.span())),
+                    format!("store {name:?} must be a table"),
+                );
+                continue;
+            };
+
+            self.check_store(store, &format!("stores.{name}"), &loc);
+            self.check_own_backends(name, store, &loc);
+
+            let locations = self.stores.entry(name.to_owned()).or_default();
+            locations.header = Some(loc(key.span()));
+
+            for (k, _) in store.iter() {
+                if let Some((key, _)) = store.get_key_value(k) {
+                    locations.keys.insert(k.to_owned(), loc(key.span()));
+                }
+            }
+        }
+    }
+
+    fn check_store(
+        &mut self,
+        store: &dyn TableLike,
+        path: &str,
+        loc: &impl Fn(Option<Range<usize>>) -> Location,
+    ) {
+        let store_keys = [store_fields(), &[EXTENDS_KEY]].concat();
+        self.check_keys(store, &store_keys, path, loc);
+
+        for kind in StoreKind::ALL {
+            for name in [Some(kind.as_str()), kind.alias()].into_iter().flatten() {
+                if let Some(table) = store.get(name).and_then(Item::as_table_like) {
+                    let path = format!("{path}.{name}");
+                    self.check_keys(table, backend_fields(&kind), &path, loc);
+                }
+            }
+        }
+
+        if let Some(policy) = store.get("policy").and_then(Item::as_table_like) {
+            let path = format!("{path}.policy");
+            self.check_keys(policy, fields::<Policy>(), &path, loc);
+        }
+    }
+
+    /// Checks that the backend tables a store defines itself match
+    /// the kind it sets itself.
+    ///
+    /// Backend tables of other kinds inherited from the defaults or
+    /// from an extended store are dropped while resolving, but the
+    /// ones written in the store itself are most likely mistakes.
+    fn check_own_backends(
+        &mut self,
+        name: &str,
+        store: &dyn TableLike,
+        loc: &impl Fn(Option<Range<usize>>) -> Location,
+    ) {
+        let Some(kind) = store.get("store").and_then(Item::as_str) else {
+            return;
+        };
+
+        let Ok(kind) = StoreKind::deserialize(Value::from(kind)) else {
+            // invalid kinds are reported once resolved
+            return;
+        };
+
+        for other in StoreKind::ALL.iter().filter(|other| **other != kind) {
+            for table in [Some(other.as_str()), other.alias()].into_iter().flatten() {
+                if let Some((key, _)) = store.get_key_value(table) {
+                    let kind = kind.as_str();
+                    let message =
+                        format!("store {name:?} has a `{table}` table, but its kind is `{kind}`");
+                    self.push(Some(loc(key.span())), message);
+                }
+            }
+        }
+    }
+
+    fn check_keys(
+        &mut self,
+        table: &dyn TableLike,
+        allowed: &[&str],
+        path: &str,
+        loc: &impl Fn(Option<Range<usize>>) -> Location,
+    ) {
+        for (name, _) in table.iter() {
+            if allowed.contains(&name) {
+                continue;
+            }
+
+            let Some((key, _)) = table.get_key_value(name) else {
+                continue;
+            };
+
+            let name = match path {
+                "" => name.to_owned(),
+                path => format!("{path}.{name}"),
+            };
+
+            let expected = allowed.join(", ");
+            let message = format!("unknown key `{name}`, expected one of: {expected}");
+            self.push(Some(loc(key.span())), message);
+        }
+    }
+
+    /// Checks the fully resolved configuration table.
+    fn check_table(&mut self, mut table: Table) {
+        // unknown keys are already reported with their location
+        retain_keys(&mut table, fields::<Config>());
+
+        if let Some(mut clipboard) = table.remove("clipboard") {
+            if let Value::Table(clipboard) = &mut clipboard {
+                retain_keys(clipboard, fields::<ClipboardConfig>());
+            }
+
+            if let Err(err) = ClipboardConfig::deserialize(clipboard) {
+                self.push(None, format!("invalid `clipboard`: {}", err.message()));
+            }
+        }
+
+        if let Some(mut agent) = table.remove("agent") {
+            if let Value::Table(agent) = &mut agent {
+                retain_keys(agent, fields::<AgentConfig>());
+            }
+
+            if let Err(err) = AgentConfig::deserialize(agent) {
+                self.push(None, format!("invalid `agent`: {}", err.message()));
+            }
+        }
+
+        if let Some(argv_secrets) = table.remove("argv-secrets") {
+            if let Err(err) = ArgvSecrets::deserialize(argv_secrets) {
+                self.push(None, format!("invalid `argv-secrets`: {}", err.message()));
+            }
+        }
+
+        let Some(Value::Table(stores)) = table.remove("stores") else {
+            return;
+        };
+
+        let mut stores: Vec<_> = stores.into_iter().collect();
+        stores.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        for (name, store) in stores {
+            let locations = self.stores.remove(&name).unwrap_or_default();
+            self.check_resolved_store(&name, store, &locations);
+        }
+    }
+
+    /// Checks a store once resolved, against the backend table of
+    /// its kind only.
+    fn check_resolved_store(&mut self, name: &str, mut store: Value, locations: &StoreLocations) {
+        let Value::Table(table) = &mut store else {
+            return;
+        };
+
+        // unknown keys are already reported with their location
+        retain_keys(table, store_fields());
+
+        // backend tables named after a kind alias are checked under
+        // their canonical name
+        for kind in StoreKind::ALL {
+            let Some(alias) = kind.alias() else {
+                continue;
+            };
+
+            let Some(backend) = table.remove(alias) else {
+                continue;
+            };
+
+            if table.contains_key(kind.as_str()) {
+                let kind = kind.as_str();
+                let message = format!("store {name:?} has both `{kind}` and `{alias}` tables");
+                self.push(locations.get(alias), message);
+            } else {
+                table.insert(kind.as_str().to_owned(), backend);
+            }
+        }
+
+        let kind = match table.get("store") {
+            None => match StoreKind::detect(|kind| table.contains_key(kind.as_str())) {
+                Some(kind) => kind,
+                None => {
+                    let message = format!(
+                        "cannot detect type of store {name:?}: no backend table for an \
+                         available backend, set `store` explicitly"
+                    );
+                    return self.push(locations.header.clone(), message);
+                }
+            },
+            Some(kind) => match StoreKind::deserialize(kind.clone().into_deserializer()) {
+                Ok(kind) => kind,
+                Err(err) => {
+                    let message = format!("invalid kind of store {name:?}: {}", err.message());
+                    return self.push(locations.get("store"), message);
+                }
+            },
+        };
+
+        if let Some(feature) = kind.missing_feature() {
+            let kind = kind.as_str();
+            let message = format!(
+                "store {name:?} uses backend `{kind}`, which is not compiled in \
+                 (enable cargo feature `{feature}`)"
+            );
+            return self.push(locations.get("store"), message);
+        }
+
+        for other in StoreKind::ALL.iter().filter(|other| **other != kind) {
+            table.remove(other.as_str());
+        }
+
+        match table.get_mut(kind.as_str()) {
+            Some(Value::Table(backend)) => retain_keys(backend, backend_fields(&kind)),
+            Some(_) => (),
+            None => {
+                let kind = kind.as_str();
+                let message = format!("store {name:?} of kind `{kind}` has no `{kind}` table");
+                return self.push(locations.get("store"), message);
+            }
+        }
+
+        if let Some(Value::Table(policy)) = table.get_mut("policy") {
+            retain_keys(policy, fields::<Policy>());
+        }
+
+        if let Err(err) = Store::deserialize(store) {
+            let message = format!("invalid store {name:?}: {}", err.message());
+            self.push(locations.header.clone(), message);
+        }
+    }
+}
+
+/// Removes the keys of the given table that are not allowed.
+fn retain_keys(table: &mut Table, allowed: &[&str]) {
+    table.retain(|key, _| allowed.contains(&key));
+}
+
+/// Returns the keys of a store table.
+fn store_fields() -> &'static [&'static str] {
+    fields::<crate::store::de::Store>()
+}
+
+/// Returns the keys of the backend table of the given kind.
+fn backend_fields(kind: &StoreKind) -> &'static [&'static str] {
+    match kind {
+        StoreKind::SecretService => fields::<SecretServiceStore>(),
+        StoreKind::LinuxKeyutils => fields::<KeyutilsStore>(),
+        StoreKind::AppleNative => fields::<MacosStore>(),
+        StoreKind::WindowsNative => fields::<WindowsStore>(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{store_fields, Checker, Diagnostic};
+    use crate::{
+        audit::Policy,
+        config::{schema::fields, Config},
+    };
+
+    fn check(name: &str, toml: &str) -> Vec<Diagnostic> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(name);
+        fs::write(&path, toml).unwrap();
+
+        let mut checker = Checker::default();
+        let (table, files) = Config::load_table(&[path]).unwrap();
+
+        for file in &files {
+            checker.check_file(file);
+        }
+
+        checker.check_table(table);
+        checker.diagnostics
+    }
+
+    #[test]
+    fn serde_fields() {
+        assert_eq!(
+            fields::<Config>(),
+            ["stores", "clipboard", "agent", "argv-secrets"]
+        );
+
+        assert!(fields::<Policy>().contains(&"min-length"));
+        assert!(store_fields().contains(&"linux-keyutils"));
+        assert!(store_fields().contains(&"keyutils"));
+        assert!(!store_fields().contains(&"extends"));
+    }
+
+    #[test]
+    #[cfg(any(feature = "dbus-secret-service", feature = "zbus-secret-service"))]
+    fn inherited_backend_of_other_kind() {
+        let diagnostics = check(
+            "inherited.toml",
+            r#"
+            [defaults]
+            store = "linux-keyutils"
+            linux-keyutils.service = "my-app"
+
+            [stores.example]
+            store = "secret-service"
+            secret-service.service = "my-app"
+            secret-service.user = "me"
+            "#,
+        );
+
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn own_backend_of_other_kind() {
+        let diagnostics = check(
+            "own.toml",
+            r#"
+            [stores.example]
+            store = "secret-service"
+            secret-service.service = "my-app"
+            secret-service.user = "me"
+            keyutils.service = "my-app"
+            "#,
+        );
+
+        let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert!(messages.contains(
+            &"store \"example\" has a `keyutils` table, but its kind is `secret-service`"
+        ));
+    }
+
+    #[test]
+    fn unknown_key_reported_once() {
+        let diagnostics = check(
+            "unknown.toml",
+            r#"
+            [stores.example]
+            store = "secret-service"
+            secret-service.service = "my-app"
+            secret-service.user = "me"
+            secret-service.usr = "me"
+            "#,
+        );
+
+        let unknown: Vec<_> = diagnostics
+            .iter()
+            .filter(|d| d.message.contains("usr"))
+            .collect();
+
+        assert_eq!(unknown.len(), 1, "{diagnostics:?}");
+        assert!(unknown[0].message.starts_with(
+            "unknown key `stores.example.secret-service.usr`, expected one of: service, user, flavour"
+        ));
+        assert_eq!(unknown[0].location.as_ref().unwrap().line, 6);
+    }
+}
@@ -0,0 +1,128 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+mod audit;
+mod config;
+mod store;
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::{CommandFactory, Parser, Subcommand};
+use pimalaya_toolbox::{
+    long_version,
+    terminal::{
+        clap::{
+            args::{ConfigPathsArg, JsonFlag, LogFlags},
+            commands::{CompletionCommand, ManualCommand},
+        },
+        printer::Printer,
+    },
+};
+
+use crate::{
+    agent::AgentCommand,
+    clipboard::ClearClipboardCommand,
+    config::Config,
+    password::PasswordCommand,
+    rotation::RotationCommand,
+    template::{var_parser, Vars},
+    totp::TotpCommand,
+};
+
+use self::{
+    audit::{AuditCommand, BreachCheckCommand},
+    config::ConfigCommand,
+    store::StoreCommand,
+};
+
+#[derive(Parser, Debug)]
+#[command(name = env!("CARGO_PKG_NAME"))]
+#[command(author, version, about)]
+#[command(long_version = long_version!())]
+#[command(propagate_version = true, infer_subcommands = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: MimosaCommand,
+    #[command(flatten)]
+    pub config: ConfigPathsArg,
+    /// Set a variable used to render store templates.
+    ///
+    /// Placeholders like `{host}` in store service and user are
+    /// replaced by the matching variable, or by the environment
+    /// variable of the same name.
+    #[arg(long = "var", global = true, value_name = "KEY=VALUE")]
+    #[arg(value_parser = var_parser)]
+    pub vars: Vec<(String, String)>,
+    #[command(flatten)]
+    pub json: JsonFlag,
+    #[command(flatten)]
+    pub log: LogFlags,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum MimosaCommand {
+    #[command(arg_required_else_help = true, subcommand)]
+    Password(PasswordCommand),
+    #[command(arg_required_else_help = true, subcommand)]
+    Totp(TotpCommand),
+    Audit(AuditCommand),
+    BreachCheck(BreachCheckCommand),
+    #[command(arg_required_else_help = true, subcommand)]
+    Rotation(RotationCommand),
+    Agent(AgentCommand),
+    #[command(arg_required_else_help = true, subcommand)]
+    Config(ConfigCommand),
+    #[command(arg_required_else_help = true, subcommand)]
+    Store(StoreCommand),
+    #[command(arg_required_else_help = true, alias = "mans")]
+    Manuals(ManualCommand),
+    #[command(arg_required_else_help = true)]
+    Completions(CompletionCommand),
+    #[command(hide = true)]
+    ClearClipboard(ClearClipboardCommand),
+}
+
+impl MimosaCommand {
+    pub fn execute(
+        self,
+        printer: &mut impl Printer,
+        config_paths: &[PathBuf],
+        vars: Vars,
+    ) -> Result<()> {
+        let load_config = || -> Result<Config> {
+            let mut config = Config::load(config_paths)?;
+            config.set_vars(vars);
+            Ok(config)
+        };
+
+        match self {
+            Self::Password(cmd) => cmd.execute(printer, &load_config()?),
+            Self::Totp(cmd) => cmd.execute(printer, &load_config()?),
+            Self::Audit(cmd) => cmd.execute(printer, &load_config()?),
+            Self::BreachCheck(cmd) => cmd.execute(printer, &load_config()?),
+            Self::Rotation(cmd) => cmd.execute(printer, &load_config()?),
+            Self::Agent(cmd) => cmd.execute(printer, &load_config()?),
+            Self::Config(cmd) => cmd.execute(printer, config_paths),
+            Self::Store(cmd) => cmd.execute(printer, &load_config()?),
+            Self::Manuals(cmd) => cmd.execute(printer, Cli::command()),
+            Self::Completions(cmd) => cmd.execute(printer, Cli::command()),
+            Self::ClearClipboard(cmd) => cmd.execute(),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+use std::fmt;
+
+use anyhow::Result;
+use clap::Parser;
+use pimalaya_toolbox::terminal::printer::Printer;
+use serde::Serialize;
+
+use crate::config::Config;
+
+/// List configured stores.
+///
+/// For each store, print its resolved backend, and whether it was
+/// detected because `store` is omitted from its configuration, with
+/// its service and user names.
+#[derive(Parser, Debug)]
+pub struct ListStoresCommand;
+
+impl ListStoresCommand {
+    pub fn execute(self, printer: &mut impl Printer, config: &Config) -> Result<()> {
+        let mut stores: Vec<StoreEntry> = config
+            .stores
+            .iter()
+            .map(|(name, store)| StoreEntry {
+                name: name.clone(),
+                store: store.backend.kind().as_str(),
+                detected: store.detected,
+                service: store.backend.service().to_owned(),
+                user: store.backend.user().to_owned(),
+            })
+            .collect();
+
+        stores.sort_by(|a, b| a.name.cmp(&b.name));
+
+        printer.out(StoreList { stores })
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct StoreList {
+    stores: Vec<StoreEntry>,
+}
+
+#[derive(Debug, Serialize)]
+struct StoreEntry {
+    name: String,
+    store: &'static str,
+    detected: bool,
+    service: String,
+    user: String,
+}
+
+impl fmt::Display for StoreList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.stores.is_empty() {
+            return write!(f, "No store configured");
+        }
+
+        for (i, store) in self.stores.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            write!(f, "{}: {}", store.name, store.store)?;
+
+            if store.detected {
+                write!(f, " (detected)")?;
+            }
+
+            write!(f, "\n - service: {}", store.service)?;
+            write!(f, "\n - user: {}", store.user)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use std::{
+    env,
+    io::{self, Write},
+    process::{Command, Stdio},
+    thread,
+    time::Duration,
+};
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use log::debug;
+use secrecy::{ExposeSecret, SecretSlice};
+use serde::{Deserialize, Serialize};
+
+use crate::{duration, memory};
+
+/// The clipboard configuration.
+///
+/// When commands are omitted, they are detected from the environment:
+/// `wl-copy`/`wl-paste` on Wayland, then `xclip` or `xsel` on X11.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ClipboardConfig {
+    /// The command receiving the secret on its stdin.
+    pub copy_command: Option<String>,
+    /// The command printing the clipboard content on its stdout.
+    pub paste_command: Option<String>,
+}
+
+/// A clipboard, driven by external copy and paste commands.
+#[derive(Clone, Debug)]
+pub struct Clipboard {
+    copy: String,
+    paste: Option<String>,
+}
+
+impl Clipboard {
+    pub fn new(config: &ClipboardConfig) -> Result<Self> {
+        if let Some(copy) = &config.copy_command {
+            return Ok(Self {
+                copy: copy.clone(),
+                paste: config.paste_command.clone(),
+            });
+        }
+
+        let wayland = env::var_os("WAYLAND_DISPLAY").is_some();
+        let x11 = env::var_os("DISPLAY").is_some();
+
+        let (copy, paste) = if wayland && is_executable("wl-copy") {
+            ("wl-copy", "wl-paste --no-newline")
+        } else if x11 && is_executable("xclip") {
+            (
+                "xclip -selection clipboard",
+                "xclip -selection clipboard -o",
+            )
+        } else if x11 && is_executable("xsel") {
+            ("xsel --clipboard --input", "xsel --clipboard --output")
+        } else {
+            bail!("Cannot find clipboard tool: install wl-copy, xclip or xsel, or configure `clipboard.copy-command`");
+        };
+
+        debug!("using clipboard commands {copy:?} and {paste:?}");
+
+        Ok(Self {
+            copy: copy.to_owned(),
+            paste: Some(config.paste_command.as_deref().unwrap_or(paste).to_owned()),
+        })
+    }
+
+    /// Places the given bytes on the clipboard.
+    pub fn copy(&self, bytes: &[u8]) -> Result<()> {
+        let mut child = command(&self.copy)?
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("Cannot spawn clipboard command {:?}", self.copy))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(bytes)
+                .context("Cannot write secret to clipboard command")?;
+        }
+
+        let status = child.wait().context("Cannot wait for clipboard command")?;
+
+        if !status.success() {
+            bail!("Clipboard command {:?} failed with {status}", self.copy);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the current clipboard content, if a paste command is
+    /// available.
+    pub fn paste(&self) -> Result<Option<SecretSlice<u8>>> {
+        let Some(paste) = &self.paste else {
+            return Ok(None);
+        };
+
+        let output = command(paste)?
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .with_context(|| format!("Cannot spawn clipboard command {paste:?}"))?;
+
+        Ok(Some(SecretSlice::from(output.stdout)))
+    }
+
+    /// Clears the clipboard if it still contains the given secret.
+    ///
+    /// When no paste command is available, the clipboard is cleared
+    /// unconditionally.
+    pub fn clear(&self, secret: &[u8]) -> Result<bool> {
+        if let Some(content) = self.paste()? {
+            if content.expose_secret() != secret {
+                debug!("clipboard content changed, skip clearing");
+                return Ok(false);
+            }
+        }
+
+        self.copy(&[])?;
+        Ok(true)
+    }
+
+    /// Clears the clipboard in the background after the given delay.
+    ///
+    /// A detached `mimosa` process is spawned, the secret being sent
+    /// through its stdin so that it never appears in its arguments.
+    pub fn clear_after(&self, secret: &[u8], delay: Duration) -> Result<()> {
+        let exe = env::current_exe().context("Cannot get current executable")?;
+
+        let mut cmd = Command::new(exe);
+        cmd.arg("clear-clipboard")
+            .arg("--after")
+            .arg(delay.as_secs().to_string())
+            .arg("--copy-command")
+            .arg(&self.copy);
+
+        if let Some(paste) = &self.paste {
+            cmd.arg("--paste-command").arg(paste);
+        }
+
+        let mut child = cmd
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .context("Cannot spawn clipboard clearing process")?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(secret)
+                .context("Cannot send secret to clipboard clearing process")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Clear the clipboard after a delay, if it still contains the
+/// secret read from stdin.
+///
+/// This command is spawned internally by `password read --clip`.
+#[derive(Parser, Debug)]
+pub struct ClearClipboardCommand {
+    /// Delay before clearing the clipboard.
+    #[arg(long, value_name = "DURATION", value_parser = duration::parser)]
+    pub after: Duration,
+
+    /// The command receiving the clipboard content on its stdin.
+    #[arg(long, value_name = "COMMAND")]
+    pub copy_command: String,
+
+    /// The command printing the clipboard content on its stdout.
+    #[arg(long, value_name = "COMMAND")]
+    pub paste_command: Option<String>,
+}
+
+impl ClearClipboardCommand {
+    pub fn execute(self) -> Result<()> {
+        let secret = memory::read_locked(io::stdin()).context("Cannot read secret from stdin")?;
+
+        thread::sleep(self.after);
+
+        let clipboard = Clipboard {
+            copy: self.copy_command,
+            paste: self.paste_command,
+        };
+
+        clipboard.clear(secret.expose_secret())?;
+
+        Ok(())
+    }
+}
+
+fn command(cmd: &str) -> Result<Command> {
+    let mut args = cmd.split_whitespace();
+
+    let Some(program) = args.next() else {
+        bail!("Clipboard command cannot be empty");
+    };
+
+    let mut cmd = Command::new(program);
+    cmd.args(args);
+    Ok(cmd)
+}
+
+fn is_executable(name: &str) -> bool {
+    let Some(paths) = env::var_os("PATH") else {
+        return false;
+    };
+
+    env::split_paths(&paths).any(|dir| dir.join(name).is_file())
+}
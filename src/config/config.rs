@@ -0,0 +1,767 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use log::{debug, warn};
+use pimalaya_toolbox::{config::TomlConfig, terminal::clap::parsers::path_parser};
+use serde::{Deserialize, Serialize};
+use toml::{Table, Value};
+
+use crate::{
+    agent::AgentConfig,
+    clipboard::ClipboardConfig,
+    config::schema::{ENV_FIELDS, ENV_PREFIX},
+    error::{Error, ErrorKind},
+    password::source::ArgvSecrets,
+    store::{
+        de::{self, StoreKind},
+        Store,
+    },
+    template::Vars,
+};
+
+/// The main configuration.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+#[non_exhaustive]
+pub struct Config {
+    /// The configuration of all the stores.
+    #[serde(default)]
+    pub stores: HashMap<String, Store>,
+
+    /// The clipboard configuration, used by `password read --clip`.
+    #[serde(default)]
+    pub(crate) clipboard: ClipboardConfig,
+
+    /// The caching agent configuration.
+    #[serde(default)]
+    pub(crate) agent: AgentConfig,
+
+    /// What to do with secrets passed as command line arguments.
+    #[serde(default, rename = "argv-secrets")]
+    pub(crate) argv_secrets: ArgvSecrets,
+
+    /// The runtime variables used to render store templates.
+    #[serde(skip)]
+    pub(crate) vars: Vars,
+}
+
+impl Config {
+    /// The name of the project-local configuration file.
+    pub const LOCAL_FILE_NAME: &'static str = ".mimosa.toml";
+
+    /// Reads, merges and parses the TOML configuration at the given
+    /// paths, or at the default path if none is given.
+    ///
+    /// Files listed in the `include` key of a configuration file are
+    /// merged before it, so that the including file takes
+    /// precedence. Include paths are shell-expanded, can contain glob
+    /// patterns and are relative to the including file. When
+    /// `local-overlay` is enabled, the closest `.mimosa.toml` found
+    /// from the current directory up to the root is merged next, and
+    /// can only define stores. Stores then inherit from the
+    /// `defaults` table, or from the store named by their `extends`
+    /// key. Finally, `MIMOSA_STORE_<NAME>_<FIELD>` environment
+    /// variables override or define stores, which allows to run
+    /// without any configuration file.
+    pub fn load(paths: &[PathBuf]) -> Result<Self, Error> {
+        let load = || -> Result<Self> {
+            let (mut table, _) = Self::load_table(paths)?;
+
+            let stores = match table.remove("stores") {
+                Some(Value::Table(stores)) => stores,
+                Some(_) => bail!("Invalid `stores`: expected a table"),
+                None => Table::new(),
+            };
+
+            let mut config: Self = Value::Table(table)
+                .try_into()
+                .context("Parse TOML config error")?;
+
+            // stores are converted outside of serde, which would
+            // erase the kind of their errors
+            for (name, store) in stores {
+                let store: de::Store = store
+                    .try_into()
+                    .with_context(|| format!("Parse TOML config error at store {name:?}"))?;
+                let store =
+                    Store::try_from(store).with_context(|| format!("Invalid store {name:?}"))?;
+                config.stores.insert(name, store);
+            }
+
+            Ok(config)
+        };
+
+        load().map_err(|err| {
+            let kind = match ErrorKind::of(&err) {
+                ErrorKind::Other => ErrorKind::Config,
+                kind => kind,
+            };
+
+            Error::new(kind, "Cannot load config").with_source(err)
+        })
+    }
+
+    /// Loads the fully merged and resolved configuration TOML table,
+    /// together with the configuration files it was merged from, in
+    /// merge order.
+    pub(crate) fn load_table(paths: &[PathBuf]) -> Result<(Table, Vec<PathBuf>)> {
+        let paths = match paths {
+            [] => match Self::first_valid_default_path() {
+                Some(path) => vec![path],
+                None => {
+                    debug!("no config file found, using environment only");
+                    Vec::new()
+                }
+            },
+            [path, ..] if !path.exists() => bail!("Invalid TOML config file paths"),
+            paths => paths.to_vec(),
+        };
+
+        let mut table = Table::new();
+        let mut files = Vec::new();
+
+        for (i, path) in paths.iter().enumerate() {
+            if i > 0 && !path.exists() {
+                debug!("skip invalid subconfig at {}", path.display());
+                continue;
+            }
+
+            merge(&mut table, read_table(path, &mut Vec::new(), &mut files)?);
+        }
+
+        let local_overlay = match table.remove("local-overlay") {
+            Some(Value::Boolean(enabled)) => enabled,
+            Some(_) => bail!("Invalid `local-overlay`: expected a boolean"),
+            None => false,
+        };
+
+        if local_overlay {
+            if let Some(path) = find_local_file()? {
+                debug!("merge local config at {}", path.display());
+                let mut local = read_table(&path, &mut Vec::new(), &mut files)?;
+                local.remove("local-overlay");
+                check_local(&path, &local)?;
+                merge(&mut table, local);
+            }
+        }
+
+        let defaults = match table.remove("defaults") {
+            Some(Value::Table(defaults)) => defaults,
+            Some(_) => bail!("Invalid `defaults`: expected a table"),
+            None => Table::new(),
+        };
+
+        if let Some(Value::Table(stores)) = table.get_mut("stores") {
+            inherit(stores, &defaults)?;
+        }
+
+        // variables that are not valid UTF-8 cannot be store fields
+        let vars = env::vars_os()
+            .filter_map(|(key, val)| Some((key.into_string().ok()?, val.into_string().ok()?)));
+        apply_env(&mut table, &defaults, vars)?;
+
+        Ok((table, files))
+    }
+
+    /// Sets the runtime variables used to render store templates.
+    ///
+    /// Placeholders without a matching variable are rendered with
+    /// the environment variable of the same name.
+    pub fn set_vars(&mut self, vars: impl IntoIterator<Item = (String, String)>) {
+        self.vars = vars.into_iter().collect();
+    }
+
+    /// Returns the store matching the given name, with its templates
+    /// rendered using the runtime variables.
+    pub fn get_store(&self, name: &str) -> Result<Store, Error> {
+        self.stores
+            .get(name)
+            .ok_or_else(|| Error::config(format!("store {name:?} not found")))?
+            .render(&self.vars)
+            .map_err(|err| Error::config(format!("Cannot render store {name:?}")).with_source(err))
+    }
+}
+
+impl TomlConfig for Config {
+    type Account = Store;
+
+    fn project_name() -> &'static str {
+        env!("CARGO_PKG_NAME")
+    }
+
+    fn find_default_account(&self) -> Option<(String, Self::Account)> {
+        None
+    }
+
+    fn find_account(&self, name: &str) -> Option<(String, Self::Account)> {
+        self.stores
+            .get(name)
+            .map(|store| (name.to_owned(), store.clone()))
+    }
+}
+
+/// Reads the TOML table at the given path, with its includes merged.
+///
+/// The stack of files being read is used to detect include cycles,
+/// and read files are collected in merge order.
+fn read_table(path: &Path, stack: &mut Vec<PathBuf>, files: &mut Vec<PathBuf>) -> Result<Table> {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_owned());
+
+    if stack.contains(&path) {
+        bail!("Include cycle detected at {}", path.display());
+    }
+
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Read TOML config file error at {}", path.display()))?;
+    let mut table: Table = content
+        .parse()
+        .with_context(|| format!("Parse TOML config error at {}", path.display()))?;
+
+    let includes = match table.remove("include") {
+        None => Vec::new(),
+        Some(Value::String(include)) => vec![include],
+        Some(Value::Array(includes)) => includes
+            .into_iter()
+            .map(|include| match include {
+                Value::String(include) => Ok(include),
+                _ => bail!("Invalid `include` in {}: expected strings", path.display()),
+            })
+            .collect::<Result<_>>()?,
+        Some(_) => bail!("Invalid `include` in {}: expected strings", path.display()),
+    };
+
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let mut merged = Table::new();
+
+    stack.push(path.clone());
+
+    for include in includes {
+        for path in resolve_include(dir, &include)? {
+            debug!("include config at {}", path.display());
+            merge(&mut merged, read_table(&path, stack, files)?);
+        }
+    }
+
+    stack.pop();
+    files.push(path);
+
+    merge(&mut merged, table);
+    Ok(merged)
+}
+
+/// Returns the paths matching the given include pattern, sorted.
+fn resolve_include(dir: &Path, include: &str) -> Result<Vec<PathBuf>> {
+    let pattern = path_parser(include).map_err(|err| anyhow!(err))?;
+    let pattern = dir.join(pattern);
+    let pattern = pattern.to_string_lossy();
+
+    let mut paths = glob::glob(&pattern)
+        .with_context(|| format!("Invalid include pattern {include:?}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("Cannot resolve include {include:?}"))?;
+
+    if paths.is_empty() && !glob_like(include) {
+        bail!("Cannot find included config {include:?}");
+    }
+
+    paths.sort();
+    Ok(paths)
+}
+
+fn glob_like(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '['])
+}
+
+/// Finds the closest local configuration file, from the current
+/// directory up to the root.
+fn find_local_file() -> Result<Option<PathBuf>> {
+    let cwd = env::current_dir().context("Cannot get current directory")?;
+
+    let path = cwd
+        .ancestors()
+        .map(|dir| dir.join(Config::LOCAL_FILE_NAME))
+        .find(|path| path.is_file());
+
+    Ok(path)
+}
+
+/// Ensures that the given local configuration only defines stores.
+///
+/// Local files come with the project rather than from the user, so
+/// they must not change settings such as the clipboard command or
+/// the agent socket.
+fn check_local(path: &Path, local: &Table) -> Result<()> {
+    match local.keys().find(|key| *key != "stores") {
+        Some(key) => bail!(
+            "Invalid `{key}` in local config {}: only `stores` can be set",
+            path.display()
+        ),
+        None => Ok(()),
+    }
+}
+
+/// Overrides or defines stores from `MIMOSA_STORE_<NAME>_<FIELD>`
+/// environment variables.
+///
+/// The store name is matched case-insensitively against existing
+/// stores, underscores matching dashes. Unknown names define new
+/// stores, lowercased, on top of the defaults. Backend fields
+/// (service, user, flavour) are set in the table of the store kind,
+/// taken from the configuration or `MIMOSA_STORE_<NAME>_KIND`, or
+/// detected otherwise. Variables with an unknown field are ignored,
+/// so that a stray variable cannot prevent loading the configuration.
+fn apply_env(
+    table: &mut Table,
+    defaults: &Table,
+    vars: impl IntoIterator<Item = (String, String)>,
+) -> Result<()> {
+    let mut overrides: Vec<(String, &str, String)> = Vec::new();
+
+    for (key, val) in vars {
+        let Some(key) = key.strip_prefix(ENV_PREFIX) else {
+            continue;
+        };
+
+        let field = ENV_FIELDS.iter().find(|field| {
+            key.strip_suffix(*field)
+                .is_some_and(|name| name.len() > 1 && name.ends_with('_'))
+        });
+
+        let Some(field) = field else {
+            warn!("ignore environment variable {ENV_PREFIX}{key}: unknown store field");
+            continue;
+        };
+
+        let name = &key[..key.len() - field.len() - 1];
+        overrides.push((name.to_owned(), field, val));
+    }
+
+    // kinds must be set first, since backend fields depend on them
+    overrides.sort_by_key(|(_, field, _)| *field != "KIND");
+
+    let stores = table
+        .entry("stores")
+        .or_insert_with(|| Value::Table(Table::new()))
+        .as_table_mut()
+        .context("Invalid `stores`: expected a table")?;
+
+    let mut touched = Vec::new();
+
+    for (name, field, val) in overrides {
+        let normalized = name.to_lowercase().replace('-', "_");
+        let name = stores
+            .keys()
+            .find(|key| key.to_lowercase().replace('-', "_") == normalized)
+            .cloned()
+            .unwrap_or(normalized);
+
+        debug!("override field {field} of store {name} from environment");
+        touched.push(name.clone());
+
+        let store = stores
+            .entry(name.clone())
+            .or_insert_with(|| Value::Table(defaults.clone()))
+            .as_table_mut()
+            .with_context(|| format!("Invalid store {name:?}: expected a table"))?;
+
+        let (key, val) = match field {
+            "KIND" => ("store", Value::String(val)),
+            "ROTATE_EVERY" => ("rotate-every", Value::String(val)),
+            "EXPIRES" => ("expires", Value::String(val)),
+            "ENTRY" => ("entry", Value::String(val)),
+            "HISTORY" => {
+                let n = val
+                    .parse()
+                    .with_context(|| format!("Invalid history size {val:?} for store {name:?}"))?;
+                ("history", Value::Integer(n))
+            }
+            field => {
+                let kind = match store.get("store") {
+                    Some(Value::String(kind)) => kind.clone(),
+                    Some(_) => bail!("Invalid kind of store {name:?}: expected a string"),
+                    None => {
+                        let has_table = |kind: &StoreKind| {
+                            [Some(kind.as_str()), kind.alias()]
+                                .into_iter()
+                                .flatten()
+                                .any(|table| store.contains_key(table))
+                        };
+
+                        let Some(kind) =
+                            StoreKind::detect(has_table).or_else(|| StoreKind::detect(|_| true))
+                        else {
+                            bail!(
+                                "Cannot detect kind of store {name:?}, set {ENV_PREFIX}{}_KIND",
+                                name.to_uppercase()
+                            );
+                        };
+
+                        kind.as_str().to_owned()
+                    }
+                };
+
+                // reuse the backend table when it is named after an
+                // alias of the kind
+                let table = match StoreKind::deserialize(Value::String(kind.clone())) {
+                    Ok(kind) => [Some(kind.as_str()), kind.alias()]
+                        .into_iter()
+                        .flatten()
+                        .find(|table| store.contains_key(*table))
+                        .unwrap_or(kind.as_str())
+                        .to_owned(),
+                    Err(_) => kind.clone(),
+                };
+
+                let backend = store
+                    .entry(table)
+                    .or_insert_with(|| Value::Table(Table::new()))
+                    .as_table_mut()
+                    .with_context(|| format!("Invalid {kind} table of store {name:?}"))?;
+
+                backend.insert(field.to_lowercase(), Value::String(val));
+                continue;
+            }
+        };
+
+        store.insert(key.to_owned(), val);
+    }
+
+    // stores seeded from the defaults, or whose kind changed, may
+    // hold backend tables of other kinds
+    for name in touched {
+        if let Some(Value::Table(store)) = stores.get_mut(&name) {
+            retain_backend(store, &Table::new());
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolves store inheritance.
+///
+/// Each store is merged on top of the store named by its `extends`
+/// key, or on top of the defaults when it does not extend any store.
+fn inherit(stores: &mut Table, defaults: &Table) -> Result<()> {
+    fn resolve(
+        name: &str,
+        raw: &Table,
+        defaults: &Table,
+        resolved: &mut Table,
+        stack: &mut Vec<String>,
+    ) -> Result<Table> {
+        if let Some(Value::Table(store)) = resolved.get(name) {
+            return Ok(store.clone());
+        }
+
+        if stack.iter().any(|n| n == name) {
+            stack.push(name.to_owned());
+            bail!("Store inheritance cycle detected: {}", stack.join(" -> "));
+        }
+
+        let mut store = match raw.get(name) {
+            Some(Value::Table(store)) => store.clone(),
+            Some(_) => bail!("Invalid store {name:?}: expected a table"),
+            None => bail!("Cannot extend store {name:?}: store not found"),
+        };
+
+        let mut base = match store.remove("extends") {
+            Some(Value::String(parent)) => {
+                stack.push(name.to_owned());
+                let base = resolve(&parent, raw, defaults, resolved, stack)?;
+                stack.pop();
+                base
+            }
+            Some(_) => bail!("Invalid `extends` of store {name:?}: expected a string"),
+            None => defaults.clone(),
+        };
+
+        let own = store.clone();
+        merge(&mut base, store);
+        retain_backend(&mut base, &own);
+        resolved.insert(name.to_owned(), Value::Table(base.clone()));
+        Ok(base)
+    }
+
+    let mut resolved = Table::new();
+
+    for name in stores.keys() {
+        resolve(name, stores, defaults, &mut resolved, &mut Vec::new())?;
+    }
+
+    *stores = resolved;
+    Ok(())
+}
+
+/// Removes the inherited backend tables that do not match the kind
+/// of the resolved store.
+///
+/// The kind is taken from the own `store` key of the store, then from
+/// its own backend table, then from the inherited `store` key. When
+/// none is found, all tables are kept for the kind to be detected.
+fn retain_backend(store: &mut Table, own: &Table) {
+    let tables = |kind: &StoreKind| [Some(kind.as_str()), kind.alias()].into_iter().flatten();
+    let parse = |kind: &Value| StoreKind::deserialize(kind.clone()).ok();
+
+    let own_table = || {
+        let mut kinds = StoreKind::ALL
+            .iter()
+            .filter(|kind| tables(kind).any(|table| own.contains_key(table)));
+
+        match (kinds.next(), kinds.next()) {
+            (Some(kind), None) => Some(kind.clone()),
+            _ => None,
+        }
+    };
+
+    let kind = match (own.get("store"), own_table()) {
+        (Some(kind), _) => parse(kind),
+        (None, Some(kind)) => {
+            // a store defining its own backend overrides the
+            // inherited kind
+            if store.contains_key("store") {
+                store.insert("store".into(), Value::from(kind.as_str()));
+            }
+
+            Some(kind)
+        }
+        (None, None) => store.get("store").and_then(parse),
+    };
+
+    let Some(kind) = kind else {
+        return;
+    };
+
+    for other in StoreKind::ALL.iter().filter(|other| **other != kind) {
+        for table in tables(other) {
+            store.remove(table);
+        }
+    }
+}
+
+/// Deeply merges the given table into the other one, values of the
+/// given table taking precedence.
+fn merge(into: &mut Table, from: Table) {
+    for (key, val) in from {
+        match (into.get_mut(&key), val) {
+            (Some(Value::Table(into)), Value::Table(from)) => merge(into, from),
+            (_, val) => {
+                into.insert(key, val);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use toml::{Table, Value};
+
+    use std::path::Path;
+
+    use super::{apply_env, check_local, inherit};
+
+    fn table(toml: &str) -> Table {
+        toml.parse().unwrap()
+    }
+
+    #[test]
+    fn defaults_of_other_kind() {
+        let defaults = table(
+            r#"
+            store = "secret-service"
+            secret-service.service = "my-app"
+            "#,
+        );
+
+        let mut stores = table(
+            r#"
+            [a]
+            secret-service.user = "me"
+
+            [b]
+            store = "linux-keyutils"
+            linux-keyutils.service = "my-app"
+            linux-keyutils.user = "me"
+
+            [c]
+            keyutils.service = "my-app"
+            keyutils.user = "me"
+            "#,
+        );
+
+        inherit(&mut stores, &defaults).unwrap();
+
+        let a = stores["a"].as_table().unwrap();
+        assert_eq!(a["store"], Value::from("secret-service"));
+        assert_eq!(
+            a["secret-service"],
+            Value::Table(table("service = \"my-app\"\nuser = \"me\""))
+        );
+
+        let b = stores["b"].as_table().unwrap();
+        assert_eq!(b["store"], Value::from("linux-keyutils"));
+        assert!(!b.contains_key("secret-service"));
+
+        // the own backend table wins over the inherited `store` key
+        let c = stores["c"].as_table().unwrap();
+        assert_eq!(c["store"], Value::from("linux-keyutils"));
+        assert!(c.contains_key("keyutils"));
+        assert!(!c.contains_key("secret-service"));
+    }
+
+    #[test]
+    fn extends_other_kind() {
+        let mut stores = table(
+            r#"
+            [a]
+            store = "secret-service"
+            secret-service.service = "my-app"
+            secret-service.user = "me"
+            history = 3
+
+            [b]
+            extends = "a"
+            store = "linux-keyutils"
+            linux-keyutils.service = "my-app"
+            linux-keyutils.user = "me"
+            "#,
+        );
+
+        inherit(&mut stores, &Table::new()).unwrap();
+
+        let b = stores["b"].as_table().unwrap();
+        assert_eq!(b["history"], Value::Integer(3));
+        assert!(b.contains_key("linux-keyutils"));
+        assert!(!b.contains_key("secret-service"));
+    }
+
+    #[test]
+    fn defaults_without_kind() {
+        let defaults = table("secret-service.service = \"my-app\"");
+        let mut stores = table("[a]\nlinux-keyutils.user = \"me\"");
+
+        inherit(&mut stores, &defaults).unwrap();
+
+        let a = stores["a"].as_table().unwrap();
+        assert!(a.contains_key("linux-keyutils"));
+        assert!(!a.contains_key("secret-service"));
+    }
+
+    fn vars(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(key, val)| (key.to_string(), val.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn env_override() {
+        let mut config = table(
+            r#"
+            [stores.my-store]
+            store = "secret-service"
+            secret-service.service = "my-app"
+            secret-service.user = "me"
+            "#,
+        );
+
+        let vars = vars(&[
+            ("MIMOSA_STORE_MY_STORE_USER", "you"),
+            ("MIMOSA_STORE_MY_STORE_HISTORY", "2"),
+            ("PATH", "/usr/bin"),
+        ]);
+
+        apply_env(&mut config, &Table::new(), vars).unwrap();
+
+        let store = config["stores"]["my-store"].as_table().unwrap();
+        assert_eq!(store["secret-service"]["service"], Value::from("my-app"));
+        assert_eq!(store["secret-service"]["user"], Value::from("you"));
+        assert_eq!(store["history"], Value::Integer(2));
+    }
+
+    #[test]
+    fn env_define() {
+        let defaults = table(
+            r#"
+            store = "secret-service"
+            secret-service.service = "my-app"
+            "#,
+        );
+
+        let mut config = Table::new();
+
+        let vars = vars(&[
+            ("MIMOSA_STORE_WORK_KIND", "linux-keyutils"),
+            ("MIMOSA_STORE_WORK_SERVICE", "work"),
+            ("MIMOSA_STORE_WORK_USER", "me"),
+            ("MIMOSA_STORE_HOME_USER", "me"),
+        ]);
+
+        apply_env(&mut config, &defaults, vars).unwrap();
+
+        let work = config["stores"]["work"].as_table().unwrap();
+        assert_eq!(work["store"], Value::from("linux-keyutils"));
+        assert_eq!(work["linux-keyutils"]["service"], Value::from("work"));
+        assert_eq!(work["linux-keyutils"]["user"], Value::from("me"));
+        assert!(!work.contains_key("secret-service"));
+
+        let home = config["stores"]["home"].as_table().unwrap();
+        assert_eq!(home["store"], Value::from("secret-service"));
+        assert_eq!(home["secret-service"]["service"], Value::from("my-app"));
+        assert_eq!(home["secret-service"]["user"], Value::from("me"));
+    }
+
+    #[test]
+    fn env_unknown_field() {
+        let mut config = Table::new();
+
+        let vars = vars(&[
+            ("MIMOSA_STORE_WORK_PASSWORD", "secret"),
+            ("MIMOSA_STORE_HOME_USER", "me"),
+        ]);
+
+        apply_env(&mut config, &Table::new(), vars).unwrap();
+
+        let stores = config["stores"].as_table().unwrap();
+        assert!(!stores.contains_key("work"));
+        assert!(stores.contains_key("home"));
+    }
+
+    #[test]
+    fn local_stores_only() {
+        let path = Path::new(".mimosa.toml");
+
+        let local = table("stores.work.secret-service.user = \"me\"");
+        assert!(check_local(path, &local).is_ok());
+
+        for toml in [
+            "clipboard.copy-command = \"sh -c evil\"",
+            "agent.socket = \"/tmp/evil.sock\"",
+            "argv-secrets = \"allow\"",
+            "defaults.store = \"secret-service\"",
+        ] {
+            let err = check_local(path, &table(toml)).unwrap_err();
+            assert!(err.to_string().contains("only `stores` can be set"));
+        }
+    }
+}
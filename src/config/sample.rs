@@ -0,0 +1,448 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! Generation of `config.sample.toml` from the configuration types.
+
+use crate::{
+    agent::AgentConfig,
+    audit::Policy,
+    config::{
+        schema::{fields, variants, ENV_FIELDS, ENV_PREFIX},
+        Config,
+    },
+    duration,
+    password::source::ArgvSecrets,
+    store::{secret_service::Flavour, EntryType, StoreKind},
+};
+
+/// Width of the comment lines.
+const WIDTH: usize = 70;
+
+/// Generates the sample configuration.
+///
+/// Keys, possible values and defaults come from the configuration
+/// types, so that `config.sample.toml` can be checked against them.
+pub fn sample() -> String {
+    let mut sample = Sample::default();
+
+    sample.top_level();
+    sample.store();
+    sample.clipboard();
+    sample.agent();
+
+    let mut sample = sample.0;
+    sample.truncate(sample.trim_end().len());
+    sample.push('\n');
+    sample
+}
+
+#[derive(Default)]
+struct Sample(String);
+
+impl Sample {
+    fn top_level(&mut self) {
+        self.key(
+            &[
+                "Other configuration files to merge before this one, so that this file \
+                 takes precedence. Paths are shell-expanded, can contain glob patterns \
+                 and are relative to this file.",
+            ],
+            &[r#"#include = ["~/.config/mimosa/work.toml", "conf.d/*.toml"]"#],
+        );
+
+        let local = format!(
+            "Merge the closest `{}` found from the current directory up to the root, \
+             on top of the whole configuration. Local files can only define stores.",
+            Config::LOCAL_FILE_NAME
+        );
+        self.key(&[&local], &["#local-overlay = true"]);
+
+        let default = value(ArgvSecrets::default());
+        let values: Vec<String> = variants::<ArgvSecrets>()
+            .iter()
+            .map(|v| match v == &default {
+                true => format!("{v:?} (default)"),
+                false => format!("{v:?}"),
+            })
+            .collect();
+        let argv = format!(
+            "What to do with secrets passed as command line arguments, which are \
+             visible to other processes and may end up in shell history. Possible \
+             values: {}.",
+            values.join(", ")
+        );
+        self.key(&[&argv], &[&format!("#argv-secrets = {default:?}")]);
+
+        let env = format!(
+            "Store fields can also be overridden, or whole stores defined, with \
+             `{ENV_PREFIX}<NAME>_<FIELD>` environment variables, where field is one of \
+             {}.",
+            ENV_FIELDS.join(", ")
+        );
+        let example = format!("For example: {ENV_PREFIX}EXAMPLE_USER=you@example.com");
+        self.note(&[&env, &example]);
+        self.0.push('\n');
+
+        self.key(
+            &[
+                "Fields shared by all stores. Each store is merged on top of these \
+               defaults, unless it extends another store.",
+            ],
+            &[
+                "#[defaults]",
+                r#"#store = "secret-service""#,
+                r#"#secret-service.service = "my-app""#,
+            ],
+        );
+    }
+
+    fn store(&mut self) {
+        self.table("stores.example");
+
+        self.key(
+            &["Inherit all fields from another store, then override some of them."],
+            &[r#"#extends = "other-store""#],
+        );
+
+        let kinds = store_kinds();
+        self.key(
+            &[
+                "The store type.",
+                "",
+                &kinds,
+                "",
+                "The backend table is named after the store type, or its alias.",
+                "",
+                "When omitted, the type is detected among the backend tables of the \
+                 store: the first backend compiled in and supported by your OS is \
+                 used, Secret Service being preferred when a D-Bus session is found, \
+                 then keyutils. Run `mimosa store list` to see the detected types.",
+            ],
+            &[r#"store = "secret-service""#],
+        );
+        self.0.push('\n');
+
+        self.key(
+            &[
+                "The keyring service name.",
+                "",
+                "Service and user names can contain `{name}` placeholders, replaced at \
+                 runtime by `--var name=value` or by the environment variable of the \
+                 same name. Use `{{` and `}}` to escape braces.",
+            ],
+            &[
+                r#"secret-service.service = "my-app""#,
+                r#"#secret-service.service = "imap.{host}""#,
+            ],
+        );
+
+        self.key(
+            &["The keyring user name."],
+            &[
+                r#"secret-service.user = "me@example.com""#,
+                r#"#secret-service.user = "{user}""#,
+            ],
+        );
+
+        let flavours = possible_values(variants::<Flavour>());
+        self.key(
+            &[
+                "The Secret Service implementation to use.\n\
+                 When omitted, the best available is chosen automatically (dbus if \
+                 available, then zbus).",
+                "",
+                &flavours,
+            ],
+            &[r#"#secret-service.flavour = "dbus""#],
+        );
+
+        let rules: Vec<String> = fields::<Policy>()
+            .iter()
+            .map(|rule| format!("#policy.{rule} = {}", policy_example(rule)))
+            .collect();
+        let rules: Vec<&str> = rules.iter().map(String::as_str).collect();
+        self.key(
+            &["The password policy checked by `mimosa audit`. All rules are optional."],
+            &rules,
+        );
+
+        self.key(
+            &[
+                "The interval after which the secret should be rotated, checked by \
+               `mimosa rotation status`. Accepts a number suffixed by s, m, h or d.\n\
+               Write times are recorded in $XDG_STATE_HOME/mimosa/state.toml.",
+            ],
+            &[r#"#rotate-every = "90d""#],
+        );
+
+        self.key(
+            &["The date after which the secret is considered expired."],
+            &[r#"#expires = "2026-12-31""#],
+        );
+
+        self.key(
+            &[
+                "The number of previous secrets to keep when writing a new one, used by \
+               `password history` and `password rollback`. Previous secrets are stored \
+               in the same backend, with the user suffixed by `~<n>`.",
+            ],
+            &["#history = 3"],
+        );
+
+        let entries = possible_values(variants::<EntryType>());
+        self.key(
+            &[
+                "The type of secret held by the store. `password read` prints TOTP \
+                 seeds as their current one-time code, and `audit` and `breach-check` \
+                 skip them.",
+                "",
+                &entries,
+            ],
+            &[&format!("#entry = {:?}", value(EntryType::default()))],
+        );
+    }
+
+    fn clipboard(&mut self) {
+        self.table("clipboard");
+
+        self.key(
+            &[
+                "The command used by `password read --clip` to place the secret on the \
+               clipboard. The secret is sent through its stdin.\n\
+               When omitted, wl-copy (Wayland), xclip or xsel (X11) is detected.",
+            ],
+            &[r#"#copy-command = "wl-copy""#],
+        );
+
+        self.key(
+            &[
+                "The command printing the current clipboard content, used to clear the \
+               clipboard only if it still contains the secret.\n\
+               When omitted with a custom copy-command, the clipboard is always \
+               cleared.",
+            ],
+            &[r#"#paste-command = "wl-paste --no-newline""#],
+        );
+    }
+
+    fn agent(&mut self) {
+        self.table("agent");
+
+        let ttl = duration::format(AgentConfig::DEFAULT_TTL);
+        self.key(
+            &[
+                "How long `mimosa agent` caches secrets. Accepts a number suffixed by s, \
+               m, h or d.",
+            ],
+            &[&format!("#ttl = {ttl:?}")],
+        );
+
+        self.key(
+            &[
+                "The agent socket path. Its directory must be owned by the current user \
+               with mode 700, otherwise the agent is not used.\n\
+               Defaults to $XDG_RUNTIME_DIR/mimosa/agent.sock.",
+            ],
+            &[r#"#socket = "/run/user/1000/mimosa/agent.sock""#],
+        );
+
+        let disable = AgentConfig::default().disable;
+        self.key(
+            &["Prevent `password read` from asking the agent first."],
+            &[&format!("#disable = {disable}")],
+        );
+    }
+
+    /// Writes a table header, separated from the previous one.
+    fn table(&mut self, name: &str) {
+        self.0.push_str(&format!("\n[{name}]\n\n"));
+    }
+
+    /// Writes the documentation of a key, followed by its lines.
+    fn key(&mut self, doc: &[&str], lines: &[&str]) {
+        self.note(doc);
+        self.0.push_str("#\n");
+
+        for line in lines {
+            self.0.push_str(line);
+            self.0.push('\n');
+        }
+
+        self.0.push('\n');
+    }
+
+    /// Writes a comment, wrapping its paragraphs.
+    ///
+    /// Empty paragraphs are written as empty comment lines, and
+    /// paragraphs starting with `#` are written as-is.
+    fn note(&mut self, paragraphs: &[&str]) {
+        for paragraph in paragraphs {
+            if paragraph.starts_with('#') {
+                self.0.push_str(paragraph);
+            } else if paragraph.is_empty() {
+                self.0.push_str("#\n");
+            } else {
+                for line in paragraph.lines() {
+                    self.0.push_str(&wrap(line));
+                }
+            }
+        }
+    }
+}
+
+/// Wraps the given text into comment lines.
+fn wrap(text: &str) -> String {
+    let mut wrapped = String::new();
+    let mut line = String::from("#");
+
+    for word in text.split_whitespace() {
+        if line.len() > 1 && line.len() + 1 + word.len() > WIDTH {
+            wrapped.push_str(&line);
+            wrapped.push('\n');
+            line = String::from("#");
+        }
+
+        line.push(' ');
+        line.push_str(word);
+    }
+
+    wrapped.push_str(&line);
+    wrapped.push('\n');
+    wrapped
+}
+
+/// Returns the serialized name of the given unit variant.
+fn value(variant: impl serde::Serialize) -> String {
+    match toml::Value::try_from(variant) {
+        Ok(toml::Value::String(name)) => name,
+        _ => unreachable!("unit variants serialize as strings"),
+    }
+}
+
+fn possible_values(values: &[&str]) -> String {
+    let values: Vec<String> = values.iter().map(|v| format!("{v:?}")).collect();
+    format!("# Possible values: {}\n", values.join(", "))
+}
+
+/// Documents the possible store types, with their description and
+/// alias.
+fn store_kinds() -> String {
+    let width = StoreKind::ALL
+        .iter()
+        .map(|kind| kind.as_str().len())
+        .max()
+        .unwrap_or_default();
+
+    let mut doc = String::from("# Possible values:\n");
+
+    for kind in StoreKind::ALL {
+        let name = format!("{:?}", kind.as_str());
+        let desc = kind.description();
+        doc.push_str(&format!("#   {name:width$} - {desc}\n", width = width + 2));
+
+        if let Some(alias) = kind.alias() {
+            let indent = " ".repeat(width + 5);
+            doc.push_str(&format!("#   {indent}alias: {alias:?}\n"));
+        }
+    }
+
+    doc
+}
+
+/// Returns the example value of the given policy rule.
+///
+/// Panics on undocumented rules, so that new ones cannot be missed.
+fn policy_example(rule: &str) -> &'static str {
+    match rule {
+        "min-length" => "16",
+        "max-length" => "64",
+        "min-entropy" => "80",
+        "require-lowercase" | "require-uppercase" | "require-digits" | "require-symbols"
+        | "forbid-dictionary" => "true",
+        rule => panic!("policy rule `{rule}` is missing from the sample"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sample;
+    use crate::{
+        agent::AgentConfig,
+        audit::Policy,
+        clipboard::ClipboardConfig,
+        config::{
+            schema::{fields, EXTENDS_KEY, LOADER_KEYS},
+            Config,
+        },
+        store::{de, secret_service::SecretServiceStore, StoreKind},
+    };
+
+    fn documented(sample: &str, key: &str) -> bool {
+        sample.lines().any(|line| {
+            let line = line.trim_start_matches('#');
+            line.starts_with(&format!("{key} =")) || line.starts_with(&format!("{key}."))
+        })
+    }
+
+    #[test]
+    fn every_key_documented() {
+        let sample = sample();
+
+        let backends: Vec<&str> = StoreKind::ALL
+            .iter()
+            .flat_map(|kind| [Some(kind.as_str()), kind.alias()])
+            .flatten()
+            .collect();
+
+        let store = fields::<de::Store>()
+            .iter()
+            .filter(|key| !backends.contains(key));
+
+        let keys = fields::<Config>()
+            .iter()
+            .chain(LOADER_KEYS)
+            .filter(|key| !["stores", "clipboard", "agent", "defaults"].contains(key))
+            .chain(store)
+            .chain([&EXTENDS_KEY])
+            .chain(fields::<ClipboardConfig>())
+            .chain(fields::<AgentConfig>());
+
+        for key in keys {
+            assert!(
+                documented(&sample, key),
+                "`{key}` is missing from the sample"
+            );
+        }
+
+        for key in fields::<SecretServiceStore>() {
+            let key = format!("secret-service.{key}");
+            assert!(
+                documented(&sample, &key),
+                "`{key}` is missing from the sample"
+            );
+        }
+
+        for rule in fields::<Policy>() {
+            let key = format!("policy.{rule}");
+            assert!(
+                documented(&sample, &key),
+                "`{key}` is missing from the sample"
+            );
+        }
+    }
+}
@@ -0,0 +1,108 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! Keys and values accepted by the configuration, captured from the
+//! types deserialized.
+
+use serde::{
+    de::{self, value, DeserializeOwned, Visitor},
+    forward_to_deserialize_any, Deserializer,
+};
+
+/// Top-level keys consumed while loading the configuration, before
+/// it is deserialized.
+pub(crate) const LOADER_KEYS: &[&str] = &["include", "local-overlay", "defaults"];
+
+/// Store key consumed while resolving inheritance.
+pub(crate) const EXTENDS_KEY: &str = "extends";
+
+/// Prefix of the environment variables overriding store fields.
+pub(crate) const ENV_PREFIX: &str = "MIMOSA_STORE_";
+
+/// Store fields that can be overridden from the environment.
+pub(crate) const ENV_FIELDS: [&str; 8] = [
+    "KIND",
+    "SERVICE",
+    "USER",
+    "FLAVOUR",
+    "ROTATE_EVERY",
+    "EXPIRES",
+    "HISTORY",
+    "ENTRY",
+];
+
+/// Returns the keys accepted by the given struct, aliases included.
+///
+/// Derived implementations of [`serde::Deserialize`] hand their keys
+/// to [`Deserializer::deserialize_struct`], where they are captured
+/// before bailing out. This keeps callers in sync with the types
+/// actually deserialized.
+pub(crate) fn fields<T: DeserializeOwned>() -> &'static [&'static str] {
+    names::<T>()
+}
+
+/// Returns the values accepted by the given unit-only enum, aliases
+/// excluded.
+///
+/// Captured the same way as [`fields`], from
+/// [`Deserializer::deserialize_enum`].
+pub(crate) fn variants<T: DeserializeOwned>() -> &'static [&'static str] {
+    names::<T>()
+}
+
+fn names<T: DeserializeOwned>() -> &'static [&'static str] {
+    let mut names: &'static [&'static str] = &[];
+    let _ = T::deserialize(NamesDeserializer(&mut names));
+    names
+}
+
+struct NamesDeserializer<'a>(&'a mut &'static [&'static str]);
+
+impl<'de> Deserializer<'de> for NamesDeserializer<'_> {
+    type Error = value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("expected a struct or an enum"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = fields;
+        Err(de::Error::custom("fields captured"))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = variants;
+        Err(de::Error::custom("variants captured"))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map identifier ignored_any
+    }
+}
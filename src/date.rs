@@ -0,0 +1,115 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context, Result};
+
+const SECS_PER_DAY: u64 = 86400;
+
+/// Parses a `YYYY-MM-DD` date into the corresponding time, at
+/// midnight UTC.
+pub fn parse(date: &str) -> Result<SystemTime> {
+    let parts: Vec<&str> = date.trim().split('-').collect();
+
+    let [y, m, d] = parts.as_slice() else {
+        bail!("Invalid date {date:?}, expected YYYY-MM-DD");
+    };
+
+    let y: i64 = y
+        .parse()
+        .with_context(|| format!("Invalid year in {date:?}"))?;
+    let m: u32 = m
+        .parse()
+        .with_context(|| format!("Invalid month in {date:?}"))?;
+    let d: u32 = d
+        .parse()
+        .with_context(|| format!("Invalid day in {date:?}"))?;
+
+    if !(1970..=9999).contains(&y) {
+        bail!("Invalid date {date:?}, expected a year between 1970 and 9999");
+    }
+
+    if !(1..=12).contains(&m) || !(1..=days_in_month(y, m)).contains(&d) {
+        bail!("Invalid date {date:?}, expected YYYY-MM-DD");
+    }
+
+    let days = days_from_civil(y, m, d) as u64;
+
+    days.checked_mul(SECS_PER_DAY)
+        .and_then(|secs| UNIX_EPOCH.checked_add(Duration::from_secs(secs)))
+        .with_context(|| format!("Invalid date {date:?}, too far in the future"))
+}
+
+/// Formats the given time as a `YYYY-MM-DD` date, in UTC.
+pub fn format(time: SystemTime) -> String {
+    let secs = secs_since_epoch(time);
+    let (y, m, d) = civil_from_days((secs / SECS_PER_DAY) as i64);
+    format!("{y:04}-{m:02}-{d:02}")
+}
+
+/// Formats the given time as a `YYYY-MM-DD HH:MM:SS` date time, in
+/// UTC.
+pub fn format_datetime(time: SystemTime) -> String {
+    let secs = secs_since_epoch(time) % SECS_PER_DAY;
+    let (h, m, s) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    format!("{} {h:02}:{m:02}:{s:02}", format(time))
+}
+
+/// Returns the number of seconds elapsed since the Unix epoch, or 0
+/// for times before it.
+pub fn secs_since_epoch(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Returns the number of days of the given month.
+fn days_in_month(y: i64, m: u32) -> u32 {
+    match m {
+        2 if y % 4 == 0 && (y % 100 != 0 || y % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// See <http://howardhinnant.github.io/date_algorithms.html>.
+fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = i64::from(m);
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + i64::from(d) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+// See <http://howardhinnant.github.io/date_algorithms.html>.
+fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    (y, m, d)
+}
@@ -0,0 +1,71 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+
+/// Parses a human-friendly duration.
+///
+/// The duration is made of an integer followed by an optional unit:
+/// `s` for seconds (default), `m` for minutes, `h` for hours and `d`
+/// for days. For example `45`, `45s`, `5m` or `90d`.
+pub fn parse(duration: &str) -> Result<Duration> {
+    let duration = duration.trim();
+    let split = duration
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(duration.len());
+    let (n, unit) = duration.split_at(split);
+
+    let n: u64 = n
+        .parse()
+        .with_context(|| format!("Invalid duration {duration:?}"))?;
+
+    let size = match unit.trim() {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 60 * 60 * 24,
+        unit => bail!("Invalid duration unit {unit:?}, expected one of s, m, h, d"),
+    };
+
+    let Some(secs) = n.checked_mul(size) else {
+        bail!("Invalid duration {duration:?}, too large");
+    };
+
+    Ok(Duration::from_secs(secs))
+}
+
+/// Clap value parser wrapper around [`parse`].
+pub fn parser(duration: &str) -> Result<Duration, String> {
+    parse(duration).map_err(|err| err.to_string())
+}
+
+/// Formats a duration using the largest unit accepted by [`parse`]
+/// that divides it exactly.
+pub fn format(duration: Duration) -> String {
+    let secs = duration.as_secs();
+
+    for (unit, size) in [("d", 86400), ("h", 3600), ("m", 60)] {
+        if secs > 0 && secs.is_multiple_of(size) {
+            return format!("{}{unit}", secs / size);
+        }
+    }
+
+    format!("{secs}s")
+}
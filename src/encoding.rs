@@ -0,0 +1,92 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use clap::ValueEnum;
+use secrecy::{ExposeSecret, SecretSlice, SecretString};
+
+/// The encoding used to exchange binary secrets with the outside
+/// world.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum Encoding {
+    /// Bytes are exchanged as-is, without any transformation.
+    Raw,
+    /// Bytes are exchanged as standard, padded base64.
+    Base64,
+    /// Bytes are exchanged as lowercase hexadecimal.
+    Hex,
+}
+
+impl Encoding {
+    /// Encodes the given secret bytes into their textual
+    /// representation.
+    ///
+    /// Raw bytes are converted lossily, since they are not meant to
+    /// be displayed.
+    pub fn encode(&self, secret: &SecretSlice<u8>) -> SecretString {
+        let bytes = secret.expose_secret();
+
+        match self {
+            Self::Raw => String::from_utf8_lossy(bytes).into_owned().into(),
+            Self::Base64 => STANDARD.encode(bytes).into(),
+            Self::Hex => hex::encode(bytes).into(),
+        }
+    }
+
+    /// Decodes the given input into secret bytes.
+    ///
+    /// Surrounding whitespaces are ignored for textual encodings, so
+    /// that trailing newlines do not break the decoding.
+    pub fn decode(&self, input: &[u8]) -> Result<SecretSlice<u8>> {
+        let bytes = match self {
+            Self::Raw => input.to_vec(),
+            Self::Base64 => STANDARD
+                .decode(input.trim_ascii())
+                .map_err(redact_base64)
+                .context("Cannot decode base64 secret")?,
+            Self::Hex => hex::decode(input.trim_ascii())
+                .map_err(redact_hex)
+                .context("Cannot decode hex secret")?,
+        };
+
+        Ok(SecretSlice::from(bytes))
+    }
+}
+
+// Decoding errors are rewritten without the invalid symbols, which
+// are part of the secret.
+
+fn redact_base64(err: base64::DecodeError) -> anyhow::Error {
+    use base64::DecodeError::*;
+
+    match err {
+        InvalidByte(offset, _) => anyhow!("Invalid symbol at offset {offset}"),
+        InvalidLastSymbol(offset, _) => anyhow!("Invalid last symbol at offset {offset}"),
+        err => err.into(),
+    }
+}
+
+fn redact_hex(err: hex::FromHexError) -> anyhow::Error {
+    match err {
+        hex::FromHexError::InvalidHexCharacter { index, .. } => {
+            anyhow!("Invalid character at position {index}")
+        }
+        err => err.into(),
+    }
+}
@@ -0,0 +1,269 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+//! Typed errors with stable kinds and exit codes.
+//!
+//! Keyring and backend errors carry an [`ErrorKind`], so that
+//! scripts can rely on the process exit code or on the `kind` of the
+//! JSON error report instead of English messages.
+
+use std::{error::Error as StdError, fmt, process};
+
+use pimalaya_toolbox::terminal::{error::ErrorReport, printer::Printer};
+use serde::Serialize;
+
+/// The kind of an error.
+///
+/// Kinds are stable, and each maps to a documented process exit
+/// code:
+///
+/// | Kind                   | Exit code |
+/// |------------------------|-----------|
+/// | `other`                | 1         |
+/// | `config`               | 3         |
+/// | `not-found`            | 4         |
+/// | `access-denied`        | 5         |
+/// | `backend-unavailable`  | 6         |
+/// | `feature-missing`      | 7         |
+/// | `platform-unsupported` | 8         |
+///
+/// Exit code 2 is used for command line usage errors.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The secret does not exist in the store.
+    NotFound,
+    /// The backend denied access to the secret, for example because
+    /// it is locked.
+    AccessDenied,
+    /// The backend could not be reached.
+    BackendUnavailable,
+    /// The backend is not compiled in.
+    FeatureMissing,
+    /// The backend is not supported by the current platform.
+    PlatformUnsupported,
+    /// The configuration is invalid.
+    Config,
+    /// Any other error.
+    Other,
+}
+
+impl ErrorKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::NotFound => "not-found",
+            Self::AccessDenied => "access-denied",
+            Self::BackendUnavailable => "backend-unavailable",
+            Self::FeatureMissing => "feature-missing",
+            Self::PlatformUnsupported => "platform-unsupported",
+            Self::Config => "config",
+            Self::Other => "other",
+        }
+    }
+
+    /// Returns the process exit code of the kind.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Other => 1,
+            Self::Config => 3,
+            Self::NotFound => 4,
+            Self::AccessDenied => 5,
+            Self::BackendUnavailable => 6,
+            Self::FeatureMissing => 7,
+            Self::PlatformUnsupported => 8,
+        }
+    }
+
+    /// Returns the kind of the innermost typed error found in the
+    /// chain of the given error with a specific kind, or
+    /// [`ErrorKind::Other`].
+    ///
+    /// Inner errors are closer to the cause, for example a missing
+    /// feature behind an invalid configuration.
+    pub fn of(err: &anyhow::Error) -> Self {
+        err.chain()
+            .filter_map(|err| err.downcast_ref::<Error>())
+            .map(Error::kind)
+            .filter(|kind| *kind != Self::Other)
+            .last()
+            .unwrap_or(Self::Other)
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// An error with a kind.
+///
+/// Its `Debug` representation shows sources through their `Display`
+/// only, since the `Debug` of some of them contains secrets.
+pub struct Error {
+    kind: ErrorKind,
+    message: String,
+    source: Option<Box<dyn StdError + Send + Sync>>,
+    reported: bool,
+}
+
+impl Error {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            source: None,
+            reported: false,
+        }
+    }
+
+    pub fn with_source(mut self, source: impl Into<Box<dyn StdError + Send + Sync>>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Marks the error as already described by the report printed
+    /// by the command, so that the JSON output stays a single
+    /// document.
+    pub fn reported(mut self) -> Self {
+        self.reported = true;
+        self
+    }
+
+    pub fn config(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Config, message)
+    }
+
+    pub fn feature_missing(feature: &str) -> Self {
+        let message = format!("Feature `{feature}` is missing");
+        Self::new(ErrorKind::FeatureMissing, message)
+    }
+
+    pub fn platform_unsupported(backend: &str) -> Self {
+        let message = format!("{backend} is not available on this platform");
+        Self::new(ErrorKind::PlatformUnsupported, message)
+    }
+
+    /// Wraps a keyring error, deriving the kind from it.
+    pub fn keyring(message: impl Into<String>, err: keyring_core::Error) -> Self {
+        use keyring_core::Error::*;
+
+        let kind = match &err {
+            NoEntry => ErrorKind::NotFound,
+            NoStorageAccess(_) => ErrorKind::AccessDenied,
+            PlatformFailure(_) | NoDefaultStore => ErrorKind::BackendUnavailable,
+            NotSupportedByStore(_) => ErrorKind::PlatformUnsupported,
+            _ => ErrorKind::Other,
+        };
+
+        let err = match err {
+            // these carry the undecodable secret
+            err @ (BadEncoding(_) | BadDataFormat(..)) => err.to_string().into(),
+            err => Box::new(err) as Box<dyn StdError + Send + Sync>,
+        };
+
+        Self::new(kind, message).with_source(err)
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Error")
+            .field("kind", &self.kind)
+            .field("message", &self.message)
+            .field("source", &self.source.as_ref().map(ToString::to_string))
+            .finish()
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.source
+            .as_deref()
+            .map(|err| err as &(dyn StdError + 'static))
+    }
+}
+
+/// Prints the error of the given result, if any, then exits with the
+/// exit code of its kind.
+///
+/// The JSON report has the shape `{"error": {"kind", "message",
+/// "sources"}}`. It is omitted for errors marked as
+/// [`reported`](Error::reported).
+pub fn eval(printer: &mut impl Printer, result: anyhow::Result<()>) {
+    let Err(err) = result else {
+        return;
+    };
+
+    let code = ErrorKind::of(&err).exit_code();
+
+    let reported = err
+        .chain()
+        .any(|err| err.downcast_ref::<Error>().is_some_and(|err| err.reported));
+
+    let printed = if printer.is_json() && reported {
+        Ok(())
+    } else if printer.is_json() {
+        printer.out(JsonReport::from(&err))
+    } else {
+        printer.out(ErrorReport::from(err))
+    };
+
+    printed.expect("Should write error report to stdout");
+    process::exit(code);
+}
+
+#[derive(Serialize)]
+struct JsonReport {
+    error: JsonError,
+}
+
+#[derive(Serialize)]
+struct JsonError {
+    kind: ErrorKind,
+    message: String,
+    sources: Vec<String>,
+}
+
+impl From<&anyhow::Error> for JsonReport {
+    fn from(err: &anyhow::Error) -> Self {
+        Self {
+            error: JsonError {
+                kind: ErrorKind::of(err),
+                message: err.to_string(),
+                sources: err.chain().skip(1).map(ToString::to_string).collect(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for JsonReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Error: {}", self.error.message)
+    }
+}
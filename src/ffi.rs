@@ -0,0 +1,216 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! C ABI, for programs linking Mimosa instead of spawning the CLI.
+//!
+//! Functions resolve stores the same way the CLI does, from the
+//! given configuration file or from the default configuration paths
+//! when `NULL`. They return `0` on success, or the exit code of the
+//! [`ErrorKind`] of the failure, as documented in the README.
+//!
+//! Like their commands, writes and removals are recorded in the
+//! rotation state and evict the secret from the agent cache.
+//!
+//! Build the shared and static libraries with the `ffi` feature:
+//!
+//! ```sh
+//! cargo rustc --release --lib --crate-type cdylib,staticlib --features ffi
+//! ```
+//!
+//! The matching header lives in `include/mimosa.h`, regenerated with
+//! `cbindgen --config cbindgen.toml --output include/mimosa.h`.
+//! `install-lib.sh` installs both along with a pkg-config file
+//! generated from `mimosa.pc.in`.
+
+use std::{
+    ffi::{c_char, c_int, CStr},
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    ptr, slice,
+};
+
+use log::debug;
+use secrecy::{ExposeSecret, SecretSlice};
+use zeroize::Zeroize;
+
+use crate::{
+    agent,
+    config::Config,
+    error::{Error, ErrorKind},
+    rotation::State,
+    store::StoreExt,
+};
+
+/// Reads the secret of the given store.
+///
+/// On success, `*secret` points to a buffer of `*len` bytes, which
+/// must be released with [`mimosa_free_secret`]. The buffer is not
+/// NUL-terminated.
+///
+/// # Safety
+///
+/// `config` must be `NULL` or a valid C string, `store` must be a
+/// valid C string, `secret` and `len` must be valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn mimosa_read(
+    config: *const c_char,
+    store: *const c_char,
+    secret: *mut *mut u8,
+    len: *mut usize,
+) -> c_int {
+    if secret.is_null() || len.is_null() {
+        return ErrorKind::Other.exit_code();
+    }
+
+    call(|| {
+        let (config, name) = load(config, store)?;
+        let bytes = config.get_store(name)?.read_secret()?;
+        let bytes: Box<[u8]> = bytes.expose_secret().into();
+        let bytes = Box::into_raw(bytes);
+
+        *len = bytes.len();
+        *secret = bytes.cast();
+
+        Ok(())
+    })
+}
+
+/// Writes `len` bytes of `secret` to the given store.
+///
+/// # Safety
+///
+/// `config` must be `NULL` or a valid C string, `store` must be a
+/// valid C string, `secret` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn mimosa_write(
+    config: *const c_char,
+    store: *const c_char,
+    secret: *const u8,
+    len: usize,
+) -> c_int {
+    if secret.is_null() && len > 0 {
+        return ErrorKind::Other.exit_code();
+    }
+
+    call(|| {
+        let bytes = match len {
+            0 => Vec::new(),
+            len => slice::from_raw_parts(secret, len).to_vec(),
+        };
+
+        let (config, name) = load(config, store)?;
+        let store = config.get_store(name)?;
+        store.write_secret(SecretSlice::from(bytes))?;
+
+        State::record_write(name, &store);
+        agent::forget(&config.agent, &store);
+
+        Ok(())
+    })
+}
+
+/// Removes the secret of the given store.
+///
+/// Returns the `not-found` exit code when there was no secret.
+///
+/// # Safety
+///
+/// `config` must be `NULL` or a valid C string, `store` must be a
+/// valid C string.
+#[no_mangle]
+pub unsafe extern "C" fn mimosa_remove(config: *const c_char, store: *const c_char) -> c_int {
+    call(|| {
+        let (config, name) = load(config, store)?;
+        let store = config.get_store(name)?;
+        let removed = store.remove()?;
+
+        if removed {
+            State::record_remove(name, &store);
+        }
+
+        agent::forget(&config.agent, &store);
+
+        if removed {
+            Ok(())
+        } else {
+            Err(Error::new(
+                ErrorKind::NotFound,
+                "Secret not found in keyring",
+            ))
+        }
+    })
+}
+
+/// Zeroizes and releases a secret returned by [`mimosa_read`].
+///
+/// # Safety
+///
+/// `secret` must be `NULL` or a buffer returned by [`mimosa_read`]
+/// with its `len`, not released yet.
+#[no_mangle]
+pub unsafe extern "C" fn mimosa_free_secret(secret: *mut u8, len: usize) {
+    if secret.is_null() {
+        return;
+    }
+
+    let mut bytes = Box::from_raw(ptr::slice_from_raw_parts_mut(secret, len));
+    bytes.zeroize();
+}
+
+/// Runs the given operation, turning errors and panics into exit
+/// codes, since neither can cross the C boundary.
+fn call(op: impl FnOnce() -> Result<(), Error>) -> c_int {
+    match panic::catch_unwind(AssertUnwindSafe(op)) {
+        Ok(Ok(())) => 0,
+        Ok(Err(err)) => {
+            debug!("{err:?}");
+            err.kind().exit_code()
+        }
+        Err(_) => {
+            debug!("mimosa operation panicked");
+            ErrorKind::Other.exit_code()
+        }
+    }
+}
+
+/// Loads the configuration and returns it with the store name.
+unsafe fn load<'a>(
+    config: *const c_char,
+    store: *const c_char,
+) -> Result<(Config, &'a str), Error> {
+    let paths = match str_arg(config, "config")? {
+        Some(path) => vec![PathBuf::from(path)],
+        None => Vec::new(),
+    };
+
+    let Some(name) = str_arg(store, "store")? else {
+        return Err(Error::new(ErrorKind::Other, "Store name is missing"));
+    };
+
+    Ok((Config::load(&paths)?, name))
+}
+
+unsafe fn str_arg<'a>(arg: *const c_char, name: &str) -> Result<Option<&'a str>, Error> {
+    if arg.is_null() {
+        return Ok(None);
+    }
+
+    CStr::from_ptr(arg).to_str().map(Some).map_err(|err| {
+        Error::new(ErrorKind::Other, format!("Invalid UTF-8 {name} argument")).with_source(err)
+    })
+}
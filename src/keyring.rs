@@ -16,44 +16,103 @@
 // License along with this program. If not, see
 // <https://www.gnu.org/licenses/>.
 
-use anyhow::{anyhow, Context, Result};
-use keyring_core::{Entry, Error};
-use secrecy::{ExposeSecret, SecretString};
+//! Low-level access to the default keyring store.
+//!
+//! The default store must be set by the backend beforehand, which
+//! [`StoreExt`](crate::store::StoreExt) implementations take care of.
 
-fn new_entry(service: &str, user: &str) -> Result<keyring_core::Entry> {
-    Entry::new(service, user)
-        .map_err(|err| anyhow!(err))
-        .context("Cannot create keyring entry")
+use std::sync::{Mutex, MutexGuard};
+
+use keyring_core::Entry;
+use secrecy::{ExposeSecret, SecretSlice, SecretString};
+use zeroize::Zeroize;
+
+use crate::error::Error;
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// Locks the default keyring store.
+///
+/// The default store is global, so the lock must be held from the
+/// moment a backend sets it until its operation completes, in case
+/// other threads use other backends.
+pub(crate) fn lock() -> MutexGuard<'static, ()> {
+    LOCK.lock().unwrap_or_else(|err| err.into_inner())
 }
 
-pub fn exists(service: &str, user: &str) -> Result<bool> {
-    match new_entry(service, user)?.get_password() {
-        Ok(_) => Ok(true),
-        Err(Error::NoEntry) => Ok(false),
-        Err(err) => Err(err.into()),
+fn new_entry(service: &str, user: &str) -> Result<Entry, Error> {
+    Entry::new(service, user).map_err(|err| Error::keyring("Cannot create keyring entry", err))
+}
+
+pub fn exists(service: &str, user: &str) -> Result<bool, Error> {
+    match new_entry(service, user)?.get_secret() {
+        Ok(mut secret) => {
+            secret.zeroize();
+            Ok(true)
+        }
+        Err(keyring_core::Error::NoEntry) => Ok(false),
+        Err(err) => Err(Error::keyring("Cannot check keyring entry", err)),
     }
 }
 
-pub fn read(service: &str, user: &str) -> Result<SecretString> {
-    let password = new_entry(service, user)?
+pub fn read(service: &str, user: &str) -> Result<SecretString, Error> {
+    let mut password = new_entry(service, user)?
         .get_password()
-        .map_err(|err| anyhow!(err))
-        .context("Cannot read password from keyring")?;
+        .map_err(|err| Error::keyring("Cannot read password from keyring", err))?;
+
+    // copy into an exact-size buffer rather than shrinking in place,
+    // which would leave the original one unzeroized
+    let secret = SecretString::from(password.as_str());
+    password.zeroize();
+
+    Ok(secret)
+}
+
+pub fn read_secret(service: &str, user: &str) -> Result<SecretSlice<u8>, Error> {
+    let mut bytes = new_entry(service, user)?
+        .get_secret()
+        .map_err(|err| Error::keyring("Cannot read secret from keyring", err))?;
 
-    Ok(SecretString::from(password))
+    let secret = SecretSlice::from(bytes.to_vec());
+    bytes.zeroize();
+
+    Ok(secret)
 }
 
-pub fn write(service: &str, user: &str, secret: SecretString) -> Result<()> {
+pub fn write(service: &str, user: &str, secret: SecretString) -> Result<(), Error> {
     new_entry(service, user)?
         .set_password(secret.expose_secret())
-        .map_err(|err| anyhow!(err))
-        .context("Cannot write password to keyring")
+        .map_err(|err| Error::keyring("Cannot write password to keyring", err))
+}
+
+pub fn write_secret(service: &str, user: &str, secret: SecretSlice<u8>) -> Result<(), Error> {
+    new_entry(service, user)?
+        .set_secret(secret.expose_secret())
+        .map_err(|err| Error::keyring("Cannot write secret to keyring", err))
 }
 
-pub fn remove(service: &str, user: &str) -> Result<bool> {
+pub fn remove(service: &str, user: &str) -> Result<bool, Error> {
     match new_entry(service, user)?.delete_credential() {
         Ok(()) => Ok(true),
-        Err(Error::NoEntry) => Ok(false),
-        Err(err) => Err(anyhow!(err).context("Cannot remove password from keyring")),
+        Err(keyring_core::Error::NoEntry) => Ok(false),
+        Err(err) => Err(Error::keyring("Cannot remove password from keyring", err)),
     }
 }
+
+/// Creates the error of a keyring store that cannot be initialized.
+#[cfg(any(
+    all(
+        any(target_os = "linux", target_os = "freebsd"),
+        any(feature = "dbus-secret-service", feature = "zbus-secret-service"),
+    ),
+    all(target_os = "linux", feature = "keyutils"),
+    all(target_os = "macos", feature = "apple-keychain"),
+    all(target_os = "windows", feature = "windows-credential-manager"),
+))]
+pub(crate) fn unavailable(backend: &str, err: impl std::fmt::Display) -> Error {
+    Error::new(
+        crate::error::ErrorKind::BackendUnavailable,
+        format!("Cannot create {backend} store"),
+    )
+    .with_source(err.to_string())
+}
@@ -19,8 +19,37 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![doc = include_str!("../README.md")]
 
-pub mod cli;
+// Stable library API, covered by semver.
+
+pub mod audit;
 pub mod config;
+pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod keyring;
-pub mod password;
 pub mod store;
+
+// CLI internals, without semver guarantees.
+
+#[doc(hidden)]
+pub mod agent;
+#[doc(hidden)]
+pub mod cli;
+#[doc(hidden)]
+pub mod clipboard;
+#[doc(hidden)]
+pub mod date;
+#[doc(hidden)]
+pub mod duration;
+#[doc(hidden)]
+pub mod encoding;
+#[doc(hidden)]
+pub mod memory;
+#[doc(hidden)]
+pub mod password;
+#[doc(hidden)]
+pub mod rotation;
+#[doc(hidden)]
+pub mod template;
+#[doc(hidden)]
+pub mod totp;
@@ -16,19 +16,29 @@
 // License along with this program. If not, see
 // <https://www.gnu.org/licenses/>.
 
+use std::env;
+
 use clap::Parser;
-use mimosa::cli::Cli;
-use pimalaya_toolbox::terminal::{error::ErrorReport, log::Logger, printer::StdoutPrinter};
+use mimosa::{cli::Cli, error, memory};
+use pimalaya_toolbox::terminal::{log::Logger, printer::StdoutPrinter};
 
 fn main() {
+    memory::harden_process();
+
     let cli = Cli::parse();
 
+    // warnings are shown unless disabled by --quiet or RUST_LOG
+    if env::var_os("RUST_LOG").is_none() {
+        env::set_var("RUST_LOG", "error,mimosa=warn");
+    }
+
     Logger::init(&cli.log);
 
     let mut printer = StdoutPrinter::new(&cli.json);
     let config_paths = cli.config.paths.as_ref();
+    let vars = cli.vars.into_iter().collect();
 
-    let result = cli.command.execute(&mut printer, config_paths);
+    let result = cli.command.execute(&mut printer, config_paths, vars);
 
-    ErrorReport::eval(&mut printer, result)
+    error::eval(&mut printer, result)
 }
@@ -0,0 +1,259 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! Helpers keeping secrets out of swap, core dumps and freed heap
+//! memory of the process.
+
+#[cfg(unix)]
+use std::{
+    collections::BTreeMap,
+    sync::{Mutex, PoisonError},
+};
+use std::{
+    fmt,
+    io::{self, Read},
+};
+
+use log::debug;
+use zeroize::Zeroize;
+
+/// The initial capacity of buffers read with [`read_locked`].
+const CHUNK: usize = 1024;
+
+/// A secret kept in locked memory.
+///
+/// Pages holding the secret are locked with `mlock(2)` so that they
+/// are never swapped to disk, then zeroized and unlocked on drop
+/// unless other secrets still live in them.
+/// Locking is best-effort: failures (for example because of
+/// `RLIMIT_MEMLOCK`) are logged and the secret is kept anyway.
+pub struct LockedSecret {
+    bytes: Box<[u8]>,
+    len: usize,
+    locked: bool,
+}
+
+impl LockedSecret {
+    pub fn new(bytes: impl Into<Box<[u8]>>) -> Self {
+        let bytes = bytes.into();
+        let len = bytes.len();
+        let locked = lock(&bytes);
+        Self { bytes, len, locked }
+    }
+
+    pub fn expose_secret(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+/// Reads the given reader to its end into locked memory.
+///
+/// Unlike [`Read::read_to_end`], the buffer is never reallocated in
+/// place: it grows by copying into a bigger locked buffer, the
+/// previous one being zeroized, so that no plaintext copy is left
+/// behind in freed memory.
+pub fn read_locked(mut reader: impl Read) -> io::Result<LockedSecret> {
+    let mut secret = LockedSecret::new(vec![0; CHUNK]);
+    secret.len = 0;
+
+    loop {
+        if secret.len == secret.bytes.len() {
+            let mut bigger = LockedSecret::new(vec![0; secret.len * 2]);
+            bigger.bytes[..secret.len].copy_from_slice(secret.expose_secret());
+            bigger.len = secret.len;
+            secret = bigger;
+        }
+
+        match reader.read(&mut secret.bytes[secret.len..]) {
+            Ok(0) => break Ok(secret),
+            Ok(n) => secret.len += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => break Err(err),
+        }
+    }
+}
+
+/// Prevents the process memory from leaking secrets outside of it.
+///
+/// Core dumps are disabled, and on Linux the process is marked as
+/// non-dumpable, which also forbids non-root processes to attach to
+/// it with `ptrace(2)`. Hardening is best-effort: failures are
+/// logged.
+#[cfg(unix)]
+pub fn harden_process() {
+    let limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+
+    // SAFETY: the limit is a valid rlimit
+    if unsafe { libc::setrlimit(libc::RLIMIT_CORE, &limit) } != 0 {
+        let err = io::Error::last_os_error();
+        debug!("cannot disable core dumps: {err}");
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    // SAFETY: PR_SET_DUMPABLE takes a single integer argument
+    if unsafe { libc::prctl(libc::PR_SET_DUMPABLE, 0) } != 0 {
+        let err = io::Error::last_os_error();
+        debug!("cannot mark process as non-dumpable: {err}");
+    }
+}
+
+#[cfg(not(unix))]
+pub fn harden_process() {
+    debug!("process hardening is not available on this platform");
+}
+
+impl fmt::Debug for LockedSecret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("LockedSecret([REDACTED])")
+    }
+}
+
+impl Drop for LockedSecret {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+
+        if self.locked {
+            unlock(&self.bytes);
+        }
+    }
+}
+
+/// Number of live locks of each page, by page address.
+///
+/// Locks do not nest: a single `munlock(2)` unlocks a page whatever
+/// the number of `mlock(2)` calls, and small secrets often share
+/// pages. A page is therefore only unlocked when the last secret
+/// it holds is.
+#[cfg(unix)]
+static PAGES: Mutex<BTreeMap<usize, usize>> = Mutex::new(BTreeMap::new());
+
+/// Returns the addresses of the pages holding the given bytes.
+#[cfg(unix)]
+fn pages(bytes: &[u8]) -> impl Iterator<Item = usize> {
+    // SAFETY: _SC_PAGESIZE is always a valid name
+    let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+    let start = bytes.as_ptr() as usize;
+    let first = start - start % size;
+    let end = start + bytes.len();
+    (first..end).step_by(size)
+}
+
+/// Locks the pages holding the given bytes in memory.
+///
+/// Returns false if they could not be locked. Locked bytes must be
+/// unlocked with [`unlock`] exactly once.
+#[cfg(unix)]
+pub fn lock(bytes: &[u8]) -> bool {
+    if bytes.is_empty() {
+        return false;
+    }
+
+    let mut pages = PAGES.lock().unwrap_or_else(PoisonError::into_inner);
+
+    // SAFETY: the pointer and length come from a valid slice
+    let res = unsafe { libc::mlock(bytes.as_ptr().cast(), bytes.len()) };
+
+    if res != 0 {
+        let err = io::Error::last_os_error();
+        debug!("cannot lock secret in memory: {err}");
+        return false;
+    }
+
+    for page in self::pages(bytes) {
+        *pages.entry(page).or_default() += 1;
+    }
+
+    true
+}
+
+#[cfg(not(unix))]
+pub fn lock(_bytes: &[u8]) -> bool {
+    debug!("memory locking is not available on this platform");
+    false
+}
+
+/// Unlocks the pages holding the given bytes that no other locked
+/// bytes share.
+#[cfg(unix)]
+pub fn unlock(bytes: &[u8]) {
+    if bytes.is_empty() {
+        return;
+    }
+
+    let mut pages = PAGES.lock().unwrap_or_else(PoisonError::into_inner);
+
+    for page in self::pages(bytes) {
+        let Some(count) = pages.get_mut(&page) else {
+            continue;
+        };
+
+        *count -= 1;
+
+        if *count == 0 {
+            pages.remove(&page);
+
+            // SAFETY: the page is mapped, since it holds the slice
+            unsafe { libc::munlock(page as *const libc::c_void, 1) };
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub fn unlock(_bytes: &[u8]) {}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::sync::PoisonError;
+
+    use super::{lock, pages, unlock, PAGES};
+
+    fn count(page: usize) -> usize {
+        let pages = PAGES.lock().unwrap_or_else(PoisonError::into_inner);
+        pages.get(&page).copied().unwrap_or_default()
+    }
+
+    #[test]
+    fn shared_page() {
+        let bytes = [0u8; 16];
+        let (a, b) = bytes.split_at(8);
+        let page = pages(a).next().unwrap();
+
+        // the bytes may straddle two pages, in which case there is
+        // nothing shared to check
+        if pages(&bytes).count() > 1 {
+            return;
+        }
+
+        if !lock(a) {
+            // locking is not permitted in this environment
+            return;
+        }
+
+        assert!(lock(b));
+        assert_eq!(count(page), 2);
+
+        unlock(a);
+        assert_eq!(count(page), 1);
+
+        unlock(b);
+        assert_eq!(count(page), 0);
+    }
+}
@@ -0,0 +1,106 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use std::fmt;
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use pimalaya_toolbox::terminal::printer::Printer;
+use secrecy::ExposeSecret;
+use serde::Serialize;
+
+use crate::config::Config;
+
+/// List the previous passwords of the store.
+///
+/// Stores keep their previous passwords when the `history` option is
+/// set in the configuration file. Versions are numbered from 1, the
+/// most recent previous password. Passwords are hidden unless --show
+/// is given.
+#[derive(Parser, Debug)]
+pub struct PasswordHistoryCommand {
+    /// Name of the store in the configuration file.
+    pub store: String,
+
+    /// Print the previous passwords.
+    #[arg(long, short)]
+    pub show: bool,
+}
+
+impl PasswordHistoryCommand {
+    pub fn execute(self, printer: &mut impl Printer, config: &Config) -> Result<()> {
+        let store = config.get_store(&self.store)?;
+
+        if store.history == 0 {
+            bail!("History is disabled for store {:?}", self.store);
+        }
+
+        let mut versions = Vec::new();
+
+        for version in 1..=store.history {
+            let Some(secret) = store.read_history(version)? else {
+                continue;
+            };
+
+            let password = self
+                .show
+                .then(|| String::from_utf8_lossy(secret.expose_secret()).into_owned());
+
+            versions.push(PasswordVersion { version, password });
+        }
+
+        printer.out(PasswordHistory {
+            store: self.store,
+            versions,
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct PasswordHistory {
+    store: String,
+    versions: Vec<PasswordVersion>,
+}
+
+#[derive(Serialize)]
+struct PasswordVersion {
+    version: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    password: Option<String>,
+}
+
+impl fmt::Display for PasswordHistory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = &self.store;
+
+        if self.versions.is_empty() {
+            return write!(f, "No previous password found in {s}");
+        }
+
+        write!(f, "Previous passwords of {s}:")?;
+
+        for v in &self.versions {
+            match &v.password {
+                Some(password) => write!(f, "\n - {}: {password}", v.version)?,
+                None => write!(f, "\n - {}: ***", v.version)?,
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -16,8 +16,11 @@
 // License along with this program. If not, see
 // <https://www.gnu.org/licenses/>.
 
+pub mod history;
 pub mod read;
 pub mod remove;
+pub mod rollback;
+pub mod source;
 pub mod write;
 
 use anyhow::Result;
@@ -27,7 +30,8 @@ use pimalaya_toolbox::terminal::printer::Printer;
 use crate::{
     config::Config,
     password::{
-        read::ReadPasswordCommand, remove::RemovePasswordCommand, write::WritePasswordCommand,
+        history::PasswordHistoryCommand, read::ReadPasswordCommand, remove::RemovePasswordCommand,
+        rollback::RollbackPasswordCommand, write::WritePasswordCommand,
     },
 };
 
@@ -39,6 +43,10 @@ pub enum PasswordCommand {
     Write(WritePasswordCommand),
     #[command(visible_aliases = ["rm", "delete", "del"])]
     Remove(RemovePasswordCommand),
+    #[command(visible_aliases = ["log", "versions"])]
+    History(PasswordHistoryCommand),
+    #[command(visible_aliases = ["restore", "revert"])]
+    Rollback(RollbackPasswordCommand),
 }
 
 impl PasswordCommand {
@@ -47,6 +55,8 @@ impl PasswordCommand {
             Self::Read(cmd) => cmd.execute(printer, config),
             Self::Write(cmd) => cmd.execute(printer, config),
             Self::Remove(cmd) => cmd.execute(printer, config),
+            Self::History(cmd) => cmd.execute(printer, config),
+            Self::Rollback(cmd) => cmd.execute(printer, config),
         }
     }
 }
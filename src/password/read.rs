@@ -16,45 +16,367 @@
 // License along with this program. If not, see
 // <https://www.gnu.org/licenses/>.
 
-use std::fmt;
+use std::{
+    fmt,
+    io::{self, Write},
+    time::Duration,
+};
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use clap::Parser;
+use log::debug;
 use pimalaya_toolbox::terminal::printer::Printer;
-use secrecy::{ExposeSecret, SecretString};
+use secrecy::{ExposeSecret, SecretSlice, SecretString};
 use serde::{ser::SerializeStruct, Serialize, Serializer};
 
-use crate::{config::Config, store::StoreExt};
+use crate::{
+    agent,
+    clipboard::Clipboard,
+    config::Config,
+    duration,
+    encoding::Encoding,
+    error::{Error, ErrorKind},
+    store::{EntryType, Store, StoreExt},
+    totp::Totp,
+};
 
 /// Read a password from the store.
 ///
 /// The raw password is printed to stdout, making it easy to pipe into
-/// other commands.
+/// other commands. Use --encoding to read binary secrets, or --clip
+/// to place the secret on the clipboard instead.
 #[derive(Parser, Debug)]
 pub struct ReadPasswordCommand {
     /// Name of the store in the configuration file.
     pub store: String,
+
+    /// Read the secret as bytes, and print it with the given
+    /// encoding.
+    ///
+    /// Raw bytes are written to stdout as-is, without trailing
+    /// newline, which makes it incompatible with --json.
+    #[arg(long, short, value_enum, value_name = "ENCODING")]
+    pub encoding: Option<Encoding>,
+
+    /// Copy the secret to the clipboard instead of printing it.
+    ///
+    /// The clipboard tool is detected from the environment (wl-copy,
+    /// xclip or xsel), unless configured in the `clipboard` section.
+    #[arg(long)]
+    pub clip: bool,
+
+    /// Clear the clipboard after the given delay, if it still
+    /// contains the secret.
+    ///
+    /// Accepts seconds or a number suffixed by s, m or h. Use 0 to
+    /// keep the secret on the clipboard.
+    #[arg(long, requires = "clip", value_name = "DURATION")]
+    #[arg(default_value = "45s", value_parser = duration::parser)]
+    pub clear_after: Duration,
+
+    /// Write the secret to the given inherited file descriptor
+    /// instead of printing it.
+    ///
+    /// The secret is written as-is, without trailing newline, and a
+    /// status is printed instead, which makes it compatible with
+    /// --json.
+    #[arg(long, value_name = "N", conflicts_with = "clip")]
+    pub to_fd: Option<i32>,
+
+    /// Read the store directly, without asking the agent first.
+    #[arg(long)]
+    pub no_agent: bool,
+
+    /// Print the given value when the store has no secret.
+    ///
+    /// The value is visible to other processes, so it is subject to
+    /// the `argv-secrets` config option.
+    #[arg(long, value_name = "VALUE", conflicts_with = "allow_missing")]
+    pub default: Option<SecretString>,
+
+    /// Succeed without printing anything when the store has no
+    /// secret.
+    ///
+    /// With --json, the password is printed as null.
+    #[arg(long, conflicts_with = "clip")]
+    pub allow_missing: bool,
 }
 
 impl ReadPasswordCommand {
     pub fn execute(self, printer: &mut impl Printer, config: &Config) -> Result<()> {
-        let password = config.get_store(&self.store)?.read()?;
-        printer.out(Password(password))
+        if self.default.is_some() {
+            config.argv_secrets.check("--default")?;
+        }
+
+        let store = config.get_store(&self.store)?;
+
+        if self.clip {
+            return self.copy_to_clipboard(printer, config, &store);
+        }
+
+        if let Some(fd) = self.to_fd {
+            return self.write_to_fd(printer, config, &store, fd);
+        }
+
+        let password = match self.encoding {
+            None => self.read_password(config, &store)?,
+            Some(Encoding::Raw) => match self.read_secret(config, &store)? {
+                Some(secret) => return write_raw(printer, secret),
+                None => None,
+            },
+            Some(encoding) => self
+                .read_secret(config, &store)?
+                .map(|secret| encoding.encode(&secret)),
+        };
+
+        match password {
+            Some(password) => printer.out(Password(Some(password))),
+            None if printer.is_json() => printer.out(Password(None)),
+            None => Ok(()),
+        }
+    }
+
+    /// Reads the secret as bytes, falling back to the default value.
+    ///
+    /// Returns `None` when the store has no secret and missing
+    /// secrets are allowed.
+    fn read_secret(&self, config: &Config, store: &Store) -> Result<Option<SecretSlice<u8>>> {
+        let secret = match store.entry() {
+            EntryType::Password => self.fetch_secret(config, store),
+            EntryType::Totp => self.fetch_code(config, store).map(into_bytes),
+        };
+
+        self.or_default(secret, into_bytes)
+    }
+
+    /// Reads the secret as text, falling back to the default value.
+    ///
+    /// Returns `None` when the store has no secret and missing
+    /// secrets are allowed.
+    fn read_password(&self, config: &Config, store: &Store) -> Result<Option<SecretString>> {
+        let password = match store.entry() {
+            EntryType::Password => self.fetch_password(config, store),
+            EntryType::Totp => self.fetch_code(config, store),
+        };
+
+        self.or_default(password, |default| default)
+    }
+
+    fn fetch_secret(&self, config: &Config, store: &Store) -> Result<SecretSlice<u8>> {
+        if !self.no_agent {
+            if let Some(secret) = agent::read(&config.agent, store)? {
+                return Ok(secret);
+            }
+        }
+
+        Ok(store.read_secret()?)
+    }
+
+    fn fetch_password(&self, config: &Config, store: &Store) -> Result<SecretString> {
+        if !self.no_agent {
+            if let Some(secret) = agent::read(&config.agent, store)? {
+                let password = String::from_utf8(secret.expose_secret().to_vec())
+                    .context("Password is not valid UTF-8, use --encoding")?;
+                return Ok(password.into());
+            }
+        }
+
+        Ok(store.read()?)
+    }
+
+    /// Generates the current one-time code from the TOTP seed of the
+    /// store.
+    fn fetch_code(&self, config: &Config, store: &Store) -> Result<SecretString> {
+        let seed = self.fetch_password(config, store)?;
+        let code = Totp::parse(seed.expose_secret())?.generate()?;
+        Ok(code.code.into())
+    }
+
+    /// Handles the not-found outcome of the given read.
+    ///
+    /// The default value is converted with the given function when
+    /// set. Otherwise `None` is returned when missing secrets are
+    /// allowed, or a not-found error.
+    fn or_default<T>(
+        &self,
+        result: Result<T>,
+        from_default: impl FnOnce(SecretString) -> T,
+    ) -> Result<Option<T>> {
+        match result {
+            Ok(secret) => Ok(Some(secret)),
+            Err(err) if ErrorKind::of(&err) == ErrorKind::NotFound => {
+                if let Some(default) = &self.default {
+                    debug!("secret not found, using default value");
+                    Ok(Some(from_default(default.clone())))
+                } else if self.allow_missing {
+                    debug!("secret not found, ignoring");
+                    Ok(None)
+                } else {
+                    let message = format!("Secret not found in store {:?}", self.store);
+                    Err(Error::new(ErrorKind::NotFound, message)
+                        .with_source(err)
+                        .into())
+                }
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn copy_to_clipboard(
+        self,
+        printer: &mut impl Printer,
+        config: &Config,
+        store: &Store,
+    ) -> Result<()> {
+        // missing secrets cannot be allowed with --clip
+        let secret = self
+            .read_bytes(config, store)?
+            .context("Secret not found")?;
+
+        let clipboard = Clipboard::new(&config.clipboard)?;
+        clipboard.copy(secret.expose_secret())?;
+
+        let clear_after = if self.clear_after.is_zero() {
+            None
+        } else {
+            clipboard.clear_after(secret.expose_secret(), self.clear_after)?;
+            Some(self.clear_after.as_secs())
+        };
+
+        printer.out(PasswordCopied {
+            store: self.store,
+            clear_after,
+        })
     }
+
+    fn write_to_fd(
+        self,
+        printer: &mut impl Printer,
+        config: &Config,
+        store: &Store,
+        fd: i32,
+    ) -> Result<()> {
+        let found = match self.read_bytes(config, store)? {
+            Some(secret) => {
+                write_fd(fd, secret.expose_secret())?;
+                true
+            }
+            None => false,
+        };
+
+        printer.out(PasswordSent {
+            store: self.store,
+            fd,
+            found,
+        })
+    }
+
+    /// Reads the secret as bytes, encoded with the given encoding if
+    /// any.
+    fn read_bytes(&self, config: &Config, store: &Store) -> Result<Option<SecretSlice<u8>>> {
+        Ok(match self.encoding {
+            None => self.read_password(config, store)?.map(into_bytes),
+            Some(Encoding::Raw) => self.read_secret(config, store)?,
+            Some(encoding) => self
+                .read_secret(config, store)?
+                .map(|secret| into_bytes(encoding.encode(&secret))),
+        })
+    }
+}
+
+fn into_bytes(secret: SecretString) -> SecretSlice<u8> {
+    SecretSlice::from(secret.expose_secret().as_bytes().to_vec())
+}
+
+fn write_raw(printer: &impl Printer, secret: SecretSlice<u8>) -> Result<()> {
+    if printer.is_json() {
+        bail!("Raw encoding cannot be used with --json");
+    }
+
+    let mut stdout = io::stdout().lock();
+
+    stdout
+        .write_all(secret.expose_secret())
+        .and_then(|()| stdout.flush())
+        .context("Cannot write raw secret to stdout")
+}
+
+/// Writes the secret to the given inherited file descriptor.
+#[cfg(unix)]
+fn write_fd(fd: i32, secret: &[u8]) -> Result<()> {
+    let mut file = crate::password::source::inherited_fd(fd)?;
+
+    file.write_all(secret)
+        .and_then(|()| file.flush())
+        .with_context(|| format!("Cannot write secret to file descriptor {fd}"))
+}
+
+#[cfg(not(unix))]
+fn write_fd(_fd: i32, _secret: &[u8]) -> Result<()> {
+    let message = "Writing secrets to file descriptors is only supported on Unix";
+    Err(Error::new(ErrorKind::PlatformUnsupported, message).into())
 }
 
-struct Password(SecretString);
+struct Password(Option<SecretString>);
 
 impl Serialize for Password {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let password = self.0.as_ref().map(ExposeSecret::expose_secret);
+
         let mut s = serializer.serialize_struct("Password", 1)?;
-        s.serialize_field("password", self.0.expose_secret())?;
+        s.serialize_field("password", &password)?;
         s.end()
     }
 }
 
 impl fmt::Display for Password {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.0.expose_secret())
+        match &self.0 {
+            Some(password) => write!(f, "{}", password.expose_secret()),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct PasswordCopied {
+    store: String,
+    clear_after: Option<u64>,
+}
+
+impl fmt::Display for PasswordCopied {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = &self.store;
+
+        match self.clear_after {
+            Some(secs) => write!(
+                f,
+                "Password from {s} copied to clipboard, cleared in {secs}s"
+            ),
+            None => write!(f, "Password from {s} copied to clipboard"),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct PasswordSent {
+    store: String,
+    fd: i32,
+    found: bool,
+}
+
+impl fmt::Display for PasswordSent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = &self.store;
+        let fd = self.fd;
+
+        if self.found {
+            write!(f, "Password from {s} written to file descriptor {fd}")
+        } else {
+            write!(
+                f,
+                "No password found in {s}, nothing written to file descriptor {fd}"
+            )
+        }
     }
 }
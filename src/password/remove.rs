@@ -23,22 +23,43 @@ use clap::Parser;
 use pimalaya_toolbox::terminal::printer::Printer;
 use serde::Serialize;
 
-use crate::{config::Config, store::StoreExt};
+use crate::{agent, config::Config, rotation::State, store::StoreExt};
 
 /// Remove a password from the store.
+///
+/// Previous passwords kept by the store history are removed as well,
+/// unless --keep-history is given.
 #[derive(Parser, Debug)]
 pub struct RemovePasswordCommand {
     /// Name of the store in the configuration file.
     pub store: String,
+
+    /// Keep the previous passwords of the store history.
+    #[arg(long)]
+    pub keep_history: bool,
 }
 
 impl RemovePasswordCommand {
     pub fn execute(self, printer: &mut impl Printer, config: &Config) -> Result<()> {
-        let removed = config.get_store(&self.store)?.remove()?;
+        let store = config.get_store(&self.store)?;
+        let removed = store.backend().remove()?;
+
+        let history = if self.keep_history {
+            0
+        } else {
+            store.remove_history()?
+        };
+
+        if removed {
+            State::record_remove(&self.store, &store);
+        }
+
+        agent::forget(&config.agent, &store);
 
         printer.out(PasswordRemoved {
             store: self.store,
             removed,
+            history,
         })
     }
 }
@@ -47,16 +68,24 @@ impl RemovePasswordCommand {
 struct PasswordRemoved {
     store: String,
     removed: bool,
+    history: usize,
 }
 
 impl fmt::Display for PasswordRemoved {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let s = &self.store;
 
-        if self.removed {
-            write!(f, "Password successfully removed from {s}")
-        } else {
-            write!(f, "No password found in {s}, nothing was removed")
+        match (self.removed, self.history) {
+            (true, 0) => write!(f, "Password successfully removed from {s}"),
+            (true, n) => write!(
+                f,
+                "Password successfully removed from {s}, along with {n} previous password(s)"
+            ),
+            (false, 0) => write!(f, "No password found in {s}, nothing was removed"),
+            (false, n) => write!(
+                f,
+                "No password found in {s}, {n} previous password(s) removed"
+            ),
         }
     }
 }
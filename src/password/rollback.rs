@@ -0,0 +1,84 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use std::fmt;
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use pimalaya_toolbox::terminal::printer::Printer;
+use serde::Serialize;
+
+use crate::{agent, config::Config, error::Error, rotation::State, store::StoreExt};
+
+/// Restore a previous password of the store.
+///
+/// The current password is moved into the history, so that a
+/// rollback can itself be rolled back.
+#[derive(Parser, Debug)]
+pub struct RollbackPasswordCommand {
+    /// Name of the store in the configuration file.
+    pub store: String,
+
+    /// The version to restore, from 1 (the most recent previous
+    /// password) to the history size of the store.
+    #[arg(long, short, value_name = "N", default_value_t = 1)]
+    pub to: usize,
+}
+
+impl RollbackPasswordCommand {
+    pub fn execute(self, printer: &mut impl Printer, config: &Config) -> Result<()> {
+        let store = config.get_store(&self.store)?;
+
+        if store.history == 0 {
+            bail!("History is disabled for store {:?}", self.store);
+        }
+
+        if !(1..=store.history).contains(&self.to) {
+            let (to, history) = (self.to, store.history);
+            let message = format!("Version {to} is out of range, expected 1 to {history}");
+            return Err(Error::config(message).into());
+        }
+
+        let Some(secret) = store.read_history(self.to)? else {
+            bail!("No version {} found in {}", self.to, self.store);
+        };
+
+        store.write_secret(secret)?;
+        State::record_write(&self.store, &store);
+        agent::forget(&config.agent, &store);
+
+        printer.out(PasswordRolledBack {
+            store: self.store,
+            version: self.to,
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct PasswordRolledBack {
+    store: String,
+    version: usize,
+}
+
+impl fmt::Display for PasswordRolledBack {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = &self.store;
+        let v = self.version;
+        write!(f, "Password of {s} successfully rolled back to version {v}")
+    }
+}
@@ -0,0 +1,162 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! Sources of secrets given to write commands.
+
+use std::{env, fs::File, io, path::Path};
+
+use anyhow::{bail, Context, Result};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use crate::memory::{self, LockedSecret};
+
+/// What to do with secrets passed as command line arguments, which
+/// are visible to other processes and may end up in shell history.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ArgvSecrets {
+    /// Accept them silently.
+    Allow,
+    /// Accept them, with a warning logged.
+    #[default]
+    Warn,
+    /// Refuse them.
+    Refuse,
+}
+
+impl ArgvSecrets {
+    /// Applies the policy to a secret passed as the given argument.
+    pub fn check(self, arg: &str) -> Result<()> {
+        match self {
+            Self::Allow => Ok(()),
+            Self::Warn => {
+                warn!(
+                    "{arg} passed as argument is visible to other processes, \
+                     see the `argv-secrets` config option"
+                );
+                Ok(())
+            }
+            Self::Refuse => {
+                bail!("Refusing {arg} passed as argument, see the `argv-secrets` config option")
+            }
+        }
+    }
+}
+
+/// How line endings and whitespace around secrets are handled.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Trim {
+    /// Keep the secret as-is.
+    Raw,
+    /// Strip a single trailing line ending, `\n` or `\r\n`, as
+    /// added by `echo` or text editors.
+    Newline,
+    /// Strip leading and trailing ASCII whitespace.
+    Whitespace,
+    /// Keep the first line only, without its line ending.
+    FirstLine,
+}
+
+impl Trim {
+    pub fn apply(self, secret: &[u8]) -> &[u8] {
+        match self {
+            Self::Raw => secret,
+            Self::Newline => secret
+                .strip_suffix(b"\r\n")
+                .or_else(|| secret.strip_suffix(b"\n"))
+                .unwrap_or(secret),
+            Self::Whitespace => secret.trim_ascii(),
+            Self::FirstLine => {
+                let line = secret.split(|b| *b == b'\n').next().unwrap_or_default();
+                line.strip_suffix(b"\r").unwrap_or(line)
+            }
+        }
+    }
+}
+
+/// Reads the secret from the file at the given path.
+pub fn read_file(path: &Path) -> Result<LockedSecret> {
+    let file = File::open(path).context("Cannot open secret file")?;
+    memory::read_locked(file).context("Cannot read secret from file")
+}
+
+/// Reads the secret from the given environment variable.
+pub fn read_env(name: &str) -> Result<LockedSecret> {
+    let Some(value) = env::var_os(name) else {
+        bail!("Cannot read secret from environment variable {name}: not set");
+    };
+
+    let mut value = value.into_string().map_err(|_| {
+        anyhow::anyhow!("Cannot read secret from environment variable {name}: invalid UTF-8")
+    })?;
+
+    let secret = LockedSecret::new(value.as_bytes());
+    value.zeroize();
+
+    Ok(secret)
+}
+
+/// Reads the secret from the given inherited file descriptor.
+#[cfg(unix)]
+pub fn read_fd(fd: i32) -> Result<LockedSecret> {
+    memory::read_locked(inherited_fd(fd)?)
+        .with_context(|| format!("Cannot read secret from file descriptor {fd}"))
+}
+
+#[cfg(not(unix))]
+pub fn read_fd(_fd: i32) -> Result<LockedSecret> {
+    use crate::error::{Error, ErrorKind};
+
+    let message = "Reading secrets from file descriptors is only supported on Unix";
+    Err(Error::new(ErrorKind::PlatformUnsupported, message).into())
+}
+
+/// Opens the given inherited file descriptor as a file.
+///
+/// The descriptor is duplicated rather than taken over, so that the
+/// returned file can be closed without closing it. Standard streams
+/// are rejected, since they carry the output of the command.
+#[cfg(unix)]
+pub fn inherited_fd(fd: i32) -> Result<File> {
+    use std::os::fd::BorrowedFd;
+
+    if (0..=2).contains(&fd) {
+        bail!("Invalid file descriptor {fd}: standard streams cannot be used");
+    }
+
+    // SAFETY: F_GETFD only queries the descriptor flags
+    if unsafe { libc::fcntl(fd, libc::F_GETFD) } == -1 {
+        let err = io::Error::last_os_error();
+        return Err(err).with_context(|| format!("Invalid file descriptor {fd}"));
+    }
+
+    // SAFETY: the descriptor is open, as checked above, and only
+    // borrowed for the time of its duplication
+    let fd = unsafe { BorrowedFd::borrow_raw(fd) }
+        .try_clone_to_owned()
+        .with_context(|| format!("Cannot duplicate file descriptor {fd}"))?;
+
+    Ok(File::from(fd))
+}
+
+/// Reads the secret from stdin.
+pub fn read_stdin() -> Result<LockedSecret> {
+    memory::read_locked(io::stdin()).context("Cannot read secret from stdin")
+}
@@ -17,61 +17,139 @@
 // <https://www.gnu.org/licenses/>.
 
 use std::{
-    fmt, fs,
-    io::{self, Read},
-    path::Path,
+    fmt,
+    path::{Path, PathBuf},
+    str,
 };
 
 use anyhow::{Context, Result};
 use clap::Parser;
+use log::warn;
 use pimalaya_toolbox::terminal::printer::Printer;
 use secrecy::{ExposeSecret, SecretString};
 use serde::Serialize;
 
-use crate::{config::Config, store::StoreExt};
+use crate::{
+    agent,
+    config::Config,
+    encoding::Encoding,
+    memory::LockedSecret,
+    password::source::{self, Trim},
+    rotation::State,
+    store::StoreExt,
+};
 
 /// Write a password into the store.
 ///
-/// If PASSWORD is given and points to an existing file, its content
-/// is used as the secret. Otherwise the argument itself is used as
-/// the secret. When no argument is provided, the secret is read from
-/// stdin (supports both piping and file redirection).
+/// The secret is read from stdin (supports both piping and file
+/// redirection), unless another source is given. Use --encoding to
+/// write binary secrets.
 #[derive(Parser, Debug)]
 pub struct WritePasswordCommand {
     /// Name of the store in the configuration file.
     pub store: String,
 
-    /// The secret, or a path to a file containing the secret.
+    /// The secret, visible to other processes: prefer other sources.
+    ///
+    /// A path to an existing file is still read for backward
+    /// compatibility, but this is deprecated in favour of
+    /// --from-file.
+    #[arg(conflicts_with_all = ["from_file", "from_env", "from_fd"])]
     pub password: Option<SecretString>,
+
+    /// Read the secret from the given file.
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["from_env", "from_fd"])]
+    pub from_file: Option<PathBuf>,
+
+    /// Read the secret from the given environment variable.
+    #[arg(long, value_name = "VAR", conflicts_with = "from_fd")]
+    pub from_env: Option<String>,
+
+    /// Read the secret from the given inherited file descriptor.
+    #[arg(long, value_name = "N")]
+    pub from_fd: Option<i32>,
+
+    /// Write the secret as bytes, decoded from the given encoding.
+    ///
+    /// Raw bytes are stored as-is, trailing newlines included.
+    #[arg(long, short, value_enum, value_name = "ENCODING")]
+    pub encoding: Option<Encoding>,
+
+    /// Store exactly the bytes given, trailing newline included.
+    ///
+    /// By default, a single trailing line ending is stripped from
+    /// secrets read from stdin, files and file descriptors.
+    #[arg(long, conflicts_with_all = ["trim", "first_line"])]
+    pub raw: bool,
+
+    /// Strip leading and trailing whitespace from the secret.
+    #[arg(long, conflicts_with = "first_line")]
+    pub trim: bool,
+
+    /// Keep the first line of the secret only.
+    #[arg(long)]
+    pub first_line: bool,
 }
 
 impl WritePasswordCommand {
     pub fn execute(self, printer: &mut impl Printer, config: &Config) -> Result<()> {
         let store = config.get_store(&self.store)?;
 
-        let password = match self.password {
-            Some(ref val) if Path::new(val.expose_secret()).is_file() => {
-                let contents = fs::read_to_string(val.expose_secret())
-                    .context("Cannot read secret from file")?;
-                contents
-                    .trim_end_matches('\n')
-                    .trim_end_matches('\r')
-                    .into()
+        let (input, stream) = match (
+            &self.password,
+            &self.from_file,
+            &self.from_env,
+            self.from_fd,
+        ) {
+            (Some(val), ..) if Path::new(val.expose_secret()).is_file() => {
+                warn!("reading a file from PASSWORD is deprecated, use --from-file");
+                (source::read_file(Path::new(val.expose_secret()))?, true)
             }
-            Some(val) => val,
-            None => {
-                let mut buf = String::new();
-                io::stdin()
-                    .read_to_string(&mut buf)
-                    .context("Cannot read secret from stdin")?;
-                buf.trim_end_matches('\n').trim_end_matches('\r').into()
+            (Some(val), ..) => {
+                config.argv_secrets.check("PASSWORD")?;
+                (LockedSecret::new(val.expose_secret().as_bytes()), false)
             }
+            (_, Some(path), ..) => (source::read_file(path)?, true),
+            (_, _, Some(name), _) => (source::read_env(name)?, false),
+            (_, _, _, Some(fd)) => (source::read_fd(fd)?, true),
+            (None, None, None, None) => (source::read_stdin()?, true),
         };
 
-        store.write(password)?;
+        let secret = self.trim_mode(stream).apply(input.expose_secret());
+
+        if let Some(encoding) = self.encoding {
+            store.write_secret(encoding.decode(secret)?)?;
+            State::record_write(&self.store, &store);
+            agent::forget(&config.agent, &store);
+            return printer.out(PasswordWritten { store: self.store });
+        }
+
+        let password = str::from_utf8(secret).context("Invalid UTF-8 secret")?;
+        store.write(password.into())?;
+        State::record_write(&self.store, &store);
+        agent::forget(&config.agent, &store);
 
         printer.out(PasswordWritten { store: self.store })
     }
+
+    /// Returns the trim mode of the secret.
+    ///
+    /// Unless given, secrets read from streams have their trailing
+    /// line ending stripped, while text secrets given as strings and
+    /// secrets decoded with --encoding are kept as-is.
+    fn trim_mode(&self, stream: bool) -> Trim {
+        if self.raw {
+            Trim::Raw
+        } else if self.trim {
+            Trim::Whitespace
+        } else if self.first_line {
+            Trim::FirstLine
+        } else if stream && self.encoding.is_none() {
+            Trim::Newline
+        } else {
+            Trim::Raw
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -0,0 +1,43 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+mod state;
+pub mod status;
+
+use anyhow::Result;
+use clap::Subcommand;
+use pimalaya_toolbox::terminal::printer::Printer;
+
+use crate::{config::Config, rotation::status::RotationStatusCommand};
+
+#[doc(inline)]
+pub use self::state::*;
+
+#[derive(Subcommand, Debug)]
+pub enum RotationCommand {
+    #[command(visible_aliases = ["st"])]
+    Status(RotationStatusCommand),
+}
+
+impl RotationCommand {
+    pub fn execute(self, printer: &mut impl Printer, config: &Config) -> Result<()> {
+        match self {
+            Self::Status(cmd) => cmd.execute(printer, config),
+        }
+    }
+}
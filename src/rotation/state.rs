@@ -0,0 +1,180 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::{date, store::Store};
+
+/// The local state, keeping track of when secrets were written.
+///
+/// Keyring backends do not expose modification dates in a portable
+/// way, so they are recorded in a TOML file located at
+/// `$XDG_STATE_HOME/mimosa/state.toml`. Secrets are keyed by store
+/// name and rendered keyring identity, so that each rendering of a
+/// templated store is tracked on its own.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct State {
+    #[serde(default)]
+    pub stores: BTreeMap<String, StoreState>,
+}
+
+/// The state of a store.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StoreState {
+    /// The Unix timestamp of the last write.
+    pub written_at: u64,
+}
+
+impl State {
+    /// Returns the path of the state file.
+    pub fn path() -> Result<PathBuf> {
+        let dir = dirs::state_dir()
+            .or_else(dirs::data_local_dir)
+            .context("Cannot find state directory")?;
+
+        Ok(dir.join(env!("CARGO_PKG_NAME")).join("state.toml"))
+    }
+
+    /// Loads the state file, or returns an empty state if it does not
+    /// exist yet.
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+
+        if !path.exists() {
+            debug!("no state file at {}, using empty state", path.display());
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Cannot read state file at {}", path.display()))?;
+
+        toml::from_str(&content)
+            .with_context(|| format!("Cannot parse state file at {}", path.display()))
+    }
+
+    /// Saves the state file, creating parent directories if needed.
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Cannot create state directory at {}", dir.display()))?;
+        }
+
+        let content = toml::to_string(self).context("Cannot serialize state")?;
+
+        fs::write(&path, content)
+            .with_context(|| format!("Cannot write state file at {}", path.display()))
+    }
+
+    /// Returns the key of the given rendered store.
+    fn key(name: &str, store: &Store) -> String {
+        let backend = store.backend();
+        format!("{name}:{}:{}", backend.service(), backend.user())
+    }
+
+    /// Returns the time of the last write of the given rendered
+    /// store.
+    pub fn written_at(&self, name: &str, store: &Store) -> Option<SystemTime> {
+        let state = self.stores.get(&Self::key(name, store))?;
+        Some(UNIX_EPOCH + Duration::from_secs(state.written_at))
+    }
+
+    /// Records a write of the given rendered store at the current
+    /// time, then saves the state.
+    ///
+    /// Failures are logged rather than returned, since they should
+    /// not prevent the secret from being written.
+    pub fn record_write(name: &str, store: &Store) {
+        let result = Self::load().and_then(|mut state| {
+            let written_at = date::secs_since_epoch(SystemTime::now());
+            state
+                .stores
+                .insert(Self::key(name, store), StoreState { written_at });
+            state.save()
+        });
+
+        if let Err(err) = result {
+            warn!("cannot record write of store {name}: {err:#}");
+        }
+    }
+
+    /// Forgets the given rendered store, then saves the state.
+    ///
+    /// Failures are logged rather than returned.
+    pub fn record_remove(name: &str, store: &Store) {
+        let result = Self::load().and_then(|mut state| {
+            if state.stores.remove(&Self::key(name, store)).is_some() {
+                state.save()?;
+            }
+            Ok(())
+        });
+
+        if let Err(err) = result {
+            warn!("cannot record removal of store {name}: {err:#}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::State;
+    use crate::store::{keyutils::KeyutilsStore, Backend, EntryType, Store};
+
+    fn render(store: &Store, user: &str) -> Store {
+        let vars = HashMap::from([("user".to_owned(), user.to_owned())]);
+        store.render(&vars).unwrap()
+    }
+
+    #[test]
+    fn key_of_rendered_store() {
+        // built by hand, since the builder needs a compiled-in backend
+        let store = Store {
+            backend: Backend::Keyutils(KeyutilsStore {
+                service: "my-app".into(),
+                user: "{user}".into(),
+            }),
+            policy: None,
+            rotate_every: None,
+            expires: None,
+            history: 0,
+            entry: EntryType::Password,
+            detected: false,
+        };
+
+        let alice = State::key("work", &render(&store, "alice"));
+        let bob = State::key("work", &render(&store, "bob"));
+
+        assert_ne!(alice, bob);
+        assert_eq!(alice, State::key("work", &render(&store, "alice")));
+        assert_ne!(alice, State::key("home", &render(&store, "alice")));
+    }
+}
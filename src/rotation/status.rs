@@ -0,0 +1,163 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use std::{fmt, time::SystemTime};
+
+use anyhow::Result;
+use clap::Parser;
+use pimalaya_toolbox::terminal::printer::Printer;
+use serde::Serialize;
+
+use crate::{config::Config, date, rotation::State};
+
+/// List stores whose secret should be rotated.
+///
+/// Only stores declaring `rotate-every` or `expires` are considered.
+/// A secret is overdue when it was last written more than
+/// `rotate-every` ago, and expired once the `expires` date is
+/// reached. Write times are only known for secrets written by
+/// Mimosa, and for templated stores, for the secret matching the
+/// given variables.
+#[derive(Parser, Debug)]
+pub struct RotationStatusCommand {
+    /// List all stores with rotation rules, not only the ones needing
+    /// attention.
+    #[arg(long, short)]
+    pub all: bool,
+}
+
+impl RotationStatusCommand {
+    pub fn execute(self, printer: &mut impl Printer, config: &Config) -> Result<()> {
+        let state = State::load()?;
+        let now = SystemTime::now();
+
+        let mut names: Vec<&String> = config.stores.keys().collect();
+        names.sort();
+
+        let mut report = RotationReport::default();
+
+        for name in names {
+            let store = &config.stores[name];
+
+            if store.rotate_every.is_none() && store.expires.is_none() {
+                continue;
+            }
+
+            // templated stores are tracked once rendered, so their
+            // write time is unknown when variables are missing
+            let written_at = match config.get_store(name) {
+                Ok(rendered) => state.written_at(name, &rendered),
+                Err(_) => None,
+            };
+
+            let due = match (written_at, store.rotate_every) {
+                // a due date too far to be represented is never reached
+                (Some(t), Some(every)) => t.checked_add(every),
+                _ => None,
+            };
+
+            let status = if store.expires.is_some_and(|t| t <= now) {
+                Status::Expired
+            } else if due.is_some_and(|t| t <= now) {
+                Status::Overdue
+            } else if written_at.is_none() && store.rotate_every.is_some() {
+                Status::Unknown
+            } else {
+                Status::Ok
+            };
+
+            if status == Status::Ok && !self.all {
+                continue;
+            }
+
+            report.stores.push(StoreRotation {
+                store: name.clone(),
+                status,
+                written_at: written_at.map(date::format_datetime),
+                due: due.map(date::format),
+                expires: store.expires.map(date::format),
+            });
+        }
+
+        printer.out(report)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum Status {
+    Ok,
+    Overdue,
+    Expired,
+    Unknown,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct RotationReport {
+    stores: Vec<StoreRotation>,
+}
+
+#[derive(Debug, Serialize)]
+struct StoreRotation {
+    store: String,
+    status: Status,
+    written_at: Option<String>,
+    due: Option<String>,
+    expires: Option<String>,
+}
+
+impl fmt::Display for RotationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.stores.is_empty() {
+            return write!(f, "No secret needs to be rotated");
+        }
+
+        let mut first = true;
+
+        for rotation in &self.stores {
+            if !first {
+                writeln!(f)?;
+            }
+
+            first = false;
+
+            let s = &rotation.store;
+
+            match rotation.status {
+                Status::Ok => write!(f, "{s}: up to date")?,
+                Status::Overdue => write!(f, "{s}: rotation overdue")?,
+                Status::Expired => write!(f, "{s}: expired")?,
+                Status::Unknown => write!(f, "{s}: never written by Mimosa")?,
+            }
+
+            if let Some(t) = &rotation.written_at {
+                write!(f, "\n - last written: {t} UTC")?;
+            }
+
+            if let Some(t) = &rotation.due {
+                write!(f, "\n - rotation due: {t}")?;
+            }
+
+            if let Some(t) = &rotation.expires {
+                write!(f, "\n - expires: {t}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
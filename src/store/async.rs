@@ -0,0 +1,203 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use std::future::Future;
+
+use log::debug;
+use secrecy::{SecretSlice, SecretString};
+
+use crate::{
+    error::Error,
+    store::{pool, Backend, Store, StoreExt},
+};
+
+/// The asynchronous counterpart of [`StoreExt`].
+///
+/// The zbus Secret Service flavour is used natively; other backends
+/// are blocking, so their operations run on a small thread pool.
+/// Futures do not depend on any async runtime.
+///
+/// Like [`StoreExt`], it only operates on the store and its history:
+/// writes neither update the rotation state nor evict the secret
+/// from the agent cache, which the CLI and the C ABI do on top of
+/// the store.
+pub trait AsyncStoreExt {
+    /// Returns `true` when the store has a secret.
+    fn exists(&self) -> impl Future<Output = Result<bool, Error>> + Send;
+    /// Reads the secret as text.
+    fn read(&self) -> impl Future<Output = Result<SecretString, Error>> + Send;
+    /// Writes the given text secret.
+    fn write(&self, secret: SecretString) -> impl Future<Output = Result<(), Error>> + Send;
+    /// Reads the secret as bytes.
+    fn read_secret(&self) -> impl Future<Output = Result<SecretSlice<u8>, Error>> + Send;
+    /// Writes the given bytes secret.
+    fn write_secret(
+        &self,
+        secret: SecretSlice<u8>,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+    /// Removes the secret, returning `false` when there was none.
+    fn remove(&self) -> impl Future<Output = Result<bool, Error>> + Send;
+}
+
+impl AsyncStoreExt for Store {
+    async fn exists(&self) -> Result<bool, Error> {
+        AsyncStoreExt::exists(&self.backend).await
+    }
+
+    async fn read(&self) -> Result<SecretString, Error> {
+        AsyncStoreExt::read(&self.backend).await
+    }
+
+    async fn write(&self, secret: SecretString) -> Result<(), Error> {
+        self.archive_async().await?;
+        AsyncStoreExt::write(&self.backend, secret).await
+    }
+
+    async fn read_secret(&self) -> Result<SecretSlice<u8>, Error> {
+        AsyncStoreExt::read_secret(&self.backend).await
+    }
+
+    async fn write_secret(&self, secret: SecretSlice<u8>) -> Result<(), Error> {
+        self.archive_async().await?;
+        AsyncStoreExt::write_secret(&self.backend, secret).await
+    }
+
+    /// Removes the secret along with its history.
+    async fn remove(&self) -> Result<bool, Error> {
+        let removed = AsyncStoreExt::remove(&self.backend).await?;
+        self.remove_history_async().await?;
+        Ok(removed)
+    }
+}
+
+impl Store {
+    /// Returns the secret written `n` writes ago, if any.
+    ///
+    /// Asynchronous counterpart of [`Store::read_history`].
+    pub async fn read_history_async(&self, n: usize) -> Result<Option<SecretSlice<u8>>, Error> {
+        let backend = self.backend.history(n);
+
+        if AsyncStoreExt::exists(&backend).await? {
+            Ok(Some(AsyncStoreExt::read_secret(&backend).await?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Removes the previous secrets, returning how many there were.
+    ///
+    /// Asynchronous counterpart of [`Store::remove_history`].
+    pub async fn remove_history_async(&self) -> Result<usize, Error> {
+        let mut removed = 0;
+
+        for n in 1..=self.history {
+            if AsyncStoreExt::remove(&self.backend.history(n)).await? {
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+
+    /// Asynchronous counterpart of `Store::archive`.
+    async fn archive_async(&self) -> Result<(), Error> {
+        if self.history == 0 {
+            return Ok(());
+        }
+
+        debug!("archive current secret, keeping {} previous", self.history);
+
+        for n in (1..self.history).rev() {
+            let next = self.backend.history(n + 1);
+
+            match self.read_history_async(n).await? {
+                Some(secret) => AsyncStoreExt::write_secret(&next, secret).await?,
+                None => {
+                    AsyncStoreExt::remove(&next).await?;
+                }
+            }
+        }
+
+        let first = self.backend.history(1);
+
+        if AsyncStoreExt::exists(&self.backend).await? {
+            let secret = AsyncStoreExt::read_secret(&self.backend).await?;
+            AsyncStoreExt::write_secret(&first, secret).await
+        } else {
+            AsyncStoreExt::remove(&first).await.map(|_| ())
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+#[cfg(feature = "zbus-secret-service")]
+macro_rules! native {
+    ($backend:expr, $op:ident $(, $arg:expr)*) => {
+        if let Backend::SecretService(s) = $backend {
+            if s.is_zbus() {
+                return crate::store::secret_service::native::$op(s $(, $arg)*).await;
+            }
+        }
+    };
+}
+
+#[cfg(not(all(
+    any(target_os = "linux", target_os = "freebsd"),
+    feature = "zbus-secret-service"
+)))]
+macro_rules! native {
+    ($backend:expr, $op:ident $(, $arg:expr)*) => {};
+}
+
+impl AsyncStoreExt for Backend {
+    async fn exists(&self) -> Result<bool, Error> {
+        native!(self, exists);
+        let backend = self.clone();
+        pool::spawn(move || StoreExt::exists(&backend)).await
+    }
+
+    async fn read(&self) -> Result<SecretString, Error> {
+        native!(self, read);
+        let backend = self.clone();
+        pool::spawn(move || StoreExt::read(&backend)).await
+    }
+
+    async fn write(&self, secret: SecretString) -> Result<(), Error> {
+        native!(self, write, secret);
+        let backend = self.clone();
+        pool::spawn(move || StoreExt::write(&backend, secret)).await
+    }
+
+    async fn read_secret(&self) -> Result<SecretSlice<u8>, Error> {
+        native!(self, read_secret);
+        let backend = self.clone();
+        pool::spawn(move || StoreExt::read_secret(&backend)).await
+    }
+
+    async fn write_secret(&self, secret: SecretSlice<u8>) -> Result<(), Error> {
+        native!(self, write_secret, secret);
+        let backend = self.clone();
+        pool::spawn(move || StoreExt::write_secret(&backend, secret)).await
+    }
+
+    async fn remove(&self) -> Result<bool, Error> {
+        native!(self, remove);
+        let backend = self.clone();
+        pool::spawn(move || StoreExt::remove(&backend)).await
+    }
+}
@@ -16,42 +16,247 @@
 // License along with this program. If not, see
 // <https://www.gnu.org/licenses/>.
 
+use std::{env, path::Path};
+
 #[allow(unused)]
 use anyhow::{bail, Context, Error};
+use log::debug;
 use serde::{Deserialize, Serialize};
 
-#[allow(unused)]
-use pimalaya_toolbox::feat;
-
-use crate::store::{
-    keyutils::KeyutilsStore, macos::MacosStore, secret_service::SecretServiceStore,
-    windows::WindowsStore,
+use crate::{
+    audit::Policy,
+    date, duration,
+    store::{
+        keyutils::KeyutilsStore, macos::MacosStore, secret_service::SecretServiceStore,
+        windows::WindowsStore, Backend,
+    },
 };
 
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 pub struct Store {
-    pub store: StoreKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub store: Option<StoreKind>,
     pub secret_service: Option<SecretServiceStore>,
+    #[serde(alias = "keyutils")]
     pub linux_keyutils: Option<KeyutilsStore>,
+    #[serde(alias = "apple-keychain")]
     pub apple_native: Option<MacosStore>,
+    #[serde(alias = "windows-credential-manager")]
     pub windows_native: Option<WindowsStore>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<Policy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rotate_every: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry: Option<EntryType>,
 }
 
-#[derive(Clone, Serialize, Deserialize)]
+/// The type of secret held by a store.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum EntryType {
+    /// A password, read as-is.
+    #[default]
+    Password,
+    /// A TOTP seed, either an `otpauth://` URI or a base32 seed,
+    /// read as its current one-time code.
+    Totp,
+}
+
+/// The kind of keyring backend of a store.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
 pub enum StoreKind {
     SecretService,
+    #[serde(alias = "keyutils")]
     LinuxKeyutils,
+    #[serde(alias = "apple-keychain")]
     AppleNative,
+    #[serde(alias = "windows-credential-manager")]
     WindowsNative,
 }
 
+impl StoreKind {
+    pub const ALL: [Self; 4] = [
+        Self::SecretService,
+        Self::LinuxKeyutils,
+        Self::AppleNative,
+        Self::WindowsNative,
+    ];
+
+    /// Returns the name of the kind, which is also the key of its
+    /// backend table.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SecretService => "secret-service",
+            Self::LinuxKeyutils => "linux-keyutils",
+            Self::AppleNative => "apple-native",
+            Self::WindowsNative => "windows-native",
+        }
+    }
+
+    /// Returns the alternative name of the kind, also accepted for its
+    /// backend table.
+    pub fn alias(&self) -> Option<&'static str> {
+        match self {
+            Self::SecretService => None,
+            Self::LinuxKeyutils => Some("keyutils"),
+            Self::AppleNative => Some("apple-keychain"),
+            Self::WindowsNative => Some("windows-credential-manager"),
+        }
+    }
+
+    /// Returns a short human-readable description of the backend.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::SecretService => "Secret Service (Linux, FreeBSD)",
+            Self::LinuxKeyutils => "Linux kernel keyutils (Linux, non-persistent)",
+            Self::AppleNative => "Apple Keychain (macOS)",
+            Self::WindowsNative => "Windows Credential Manager (Windows)",
+        }
+    }
+
+    /// Returns `true` when the backend of the kind is compiled in
+    /// and supported by the current platform.
+    pub fn is_supported(&self) -> bool {
+        let platform = match self {
+            Self::SecretService => cfg!(any(target_os = "linux", target_os = "freebsd")),
+            Self::LinuxKeyutils => cfg!(target_os = "linux"),
+            Self::AppleNative => cfg!(target_os = "macos"),
+            Self::WindowsNative => cfg!(target_os = "windows"),
+        };
+
+        platform && self.missing_feature().is_none()
+    }
+
+    /// Returns `true` when the backend of the kind looks reachable.
+    ///
+    /// Only Secret Service is probed, by looking for a D-Bus session
+    /// bus, other backends being always reachable when supported.
+    pub fn is_reachable(&self) -> bool {
+        let Self::SecretService = self else {
+            return true;
+        };
+
+        let address = env::var_os("DBUS_SESSION_BUS_ADDRESS").is_some_and(|a| !a.is_empty());
+        let socket = env::var_os("XDG_RUNTIME_DIR")
+            .map(|dir| Path::new(&dir).join("bus"))
+            .is_some_and(|path| path.exists());
+
+        debug!("D-Bus session bus address set: {address}, socket found: {socket}");
+        address || socket
+    }
+
+    /// Detects the first supported kind matching the given
+    /// predicate, in order of preference.
+    ///
+    /// Reachable backends are preferred, so that keyutils is picked
+    /// over Secret Service when no D-Bus session is available. There
+    /// is deliberately no file backend to fall back to: secrets are
+    /// only kept in keyrings.
+    pub fn detect(predicate: impl Fn(&Self) -> bool) -> Option<Self> {
+        let candidates = || {
+            Self::ALL
+                .into_iter()
+                .filter(|kind| kind.is_supported() && predicate(kind))
+        };
+
+        candidates()
+            .find(Self::is_reachable)
+            .or_else(|| candidates().next())
+    }
+
+    /// Returns the cargo feature to enable when the backend of the
+    /// kind is not compiled in.
+    pub fn missing_feature(&self) -> Option<&'static str> {
+        let (enabled, feature) = match self {
+            Self::SecretService => (
+                cfg!(any(
+                    feature = "dbus-secret-service",
+                    feature = "zbus-secret-service"
+                )),
+                "dbus-secret-service",
+            ),
+            Self::LinuxKeyutils => (cfg!(feature = "keyutils"), "keyutils"),
+            Self::AppleNative => (cfg!(feature = "apple-keychain"), "apple-keychain"),
+            Self::WindowsNative => (
+                cfg!(feature = "windows-credential-manager"),
+                "windows-credential-manager",
+            ),
+        };
+
+        (!enabled).then_some(feature)
+    }
+}
+
+impl Store {
+    /// Returns `true` when the store has a backend table for the
+    /// given kind, under its name or its alias.
+    pub fn has_table(&self, kind: &StoreKind) -> bool {
+        match kind {
+            StoreKind::SecretService => self.secret_service.is_some(),
+            StoreKind::LinuxKeyutils => self.linux_keyutils.is_some(),
+            StoreKind::AppleNative => self.apple_native.is_some(),
+            StoreKind::WindowsNative => self.windows_native.is_some(),
+        }
+    }
+
+    /// Returns the kind of the store, detected among its backend
+    /// tables when `store` is omitted.
+    pub fn kind(&self) -> Result<StoreKind, Error> {
+        if let Some(kind) = &self.store {
+            return Ok(kind.clone());
+        }
+
+        let Some(kind) = StoreKind::detect(|kind| self.has_table(kind)) else {
+            bail!("cannot detect store type: no backend table for an available backend, set `store` explicitly");
+        };
+
+        debug!("store type not set, detected `{}`", kind.as_str());
+        Ok(kind)
+    }
+}
+
 impl TryFrom<Store> for super::store::Store {
     type Error = Error;
 
+    fn try_from(mut entry: Store) -> Result<Self, Self::Error> {
+        let policy = entry.policy.take();
+
+        let rotate_every = match entry.rotate_every.take() {
+            Some(d) => Some(duration::parse(&d).context("invalid `rotate-every`")?),
+            None => None,
+        };
+
+        let expires = match entry.expires.take() {
+            Some(d) => Some(date::parse(&d).context("invalid `expires`")?),
+            None => None,
+        };
+
+        Ok(Self {
+            history: entry.history.take().unwrap_or_default(),
+            entry: entry.entry.take().unwrap_or_default(),
+            detected: entry.store.is_none(),
+            backend: Backend::try_from(entry)?,
+            policy,
+            rotate_every,
+            expires,
+        })
+    }
+}
+
+impl TryFrom<Store> for Backend {
+    type Error = Error;
+
     fn try_from(entry: Store) -> Result<Self, Self::Error> {
-        match entry.store {
+        match entry.kind()? {
             #[cfg(any(feature = "dbus-secret-service", feature = "zbus-secret-service"))]
             StoreKind::SecretService => {
                 let store = entry
@@ -61,73 +266,112 @@ impl TryFrom<Store> for super::store::Store {
             }
             #[cfg(not(any(feature = "dbus-secret-service", feature = "zbus-secret-service")))]
             StoreKind::SecretService => {
-                bail!("missing feature: enable `dbus-secret-service` or `zbus-secret-service`");
+                Err(crate::error::Error::feature_missing("dbus-secret-service").into())
             }
 
             #[cfg(feature = "keyutils")]
             StoreKind::LinuxKeyutils => {
                 let store = entry
                     .linux_keyutils
-                    .context("missing `keyutils` configuration")?;
-                Ok(Self::LinuxKeyutils(store))
+                    .context("missing `linux-keyutils` configuration")?;
+                Ok(Self::Keyutils(store))
             }
             #[cfg(not(feature = "keyutils"))]
-            StoreKind::LinuxKeyutils => bail!(feat!("keyutils")),
+            StoreKind::LinuxKeyutils => {
+                Err(crate::error::Error::feature_missing("keyutils").into())
+            }
 
             #[cfg(feature = "apple-keychain")]
             StoreKind::AppleNative => {
                 let store = entry
                     .apple_native
-                    .context("missing `apple-keychain` configuration")?;
+                    .context("missing `apple-native` configuration")?;
                 Ok(Self::Macos(store))
             }
             #[cfg(not(feature = "apple-keychain"))]
-            StoreKind::AppleNative => bail!(feat!("apple-keychain")),
+            StoreKind::AppleNative => {
+                Err(crate::error::Error::feature_missing("apple-keychain").into())
+            }
 
             #[cfg(feature = "windows-credential-manager")]
             StoreKind::WindowsNative => {
                 let store = entry
                     .windows_native
-                    .context("missing `windows-credential-manager` configuration")?;
+                    .context("missing `windows-native` configuration")?;
                 Ok(Self::Windows(store))
             }
             #[cfg(not(feature = "windows-credential-manager"))]
-            StoreKind::WindowsNative => bail!(feat!("windows-credential-manager")),
+            StoreKind::WindowsNative => {
+                Err(crate::error::Error::feature_missing("windows-credential-manager").into())
+            }
         }
     }
 }
 
 impl From<super::store::Store> for Store {
     fn from(store: super::store::Store) -> Self {
-        match store {
-            super::store::Store::SecretService(s) => Self {
-                store: StoreKind::SecretService,
+        let policy = store.policy;
+        let rotate_every = store.rotate_every.map(duration::format);
+        let expires = store.expires.map(date::format);
+        let history = Some(store.history).filter(|n| *n > 0);
+        let entry_type = Some(store.entry).filter(|entry| *entry != EntryType::Password);
+        let detected = store.detected;
+
+        let mut entry = match store.backend {
+            Backend::SecretService(s) => Self {
+                store: Some(StoreKind::SecretService),
                 secret_service: Some(s),
                 linux_keyutils: None,
                 apple_native: None,
                 windows_native: None,
+                policy,
+                rotate_every,
+                expires,
+                history,
+                entry: entry_type,
             },
-            super::store::Store::Keyutils(s) => Self {
-                store: StoreKind::LinuxKeyutils,
+            Backend::Keyutils(s) => Self {
+                store: Some(StoreKind::LinuxKeyutils),
                 secret_service: None,
                 linux_keyutils: Some(s),
                 apple_native: None,
                 windows_native: None,
+                policy,
+                rotate_every,
+                expires,
+                history,
+                entry: entry_type,
             },
-            super::store::Store::Macos(s) => Self {
-                store: StoreKind::AppleNative,
+            Backend::Macos(s) => Self {
+                store: Some(StoreKind::AppleNative),
                 secret_service: None,
                 linux_keyutils: None,
                 apple_native: Some(s),
                 windows_native: None,
+                policy,
+                rotate_every,
+                expires,
+                history,
+                entry: entry_type,
             },
-            super::store::Store::Windows(s) => Self {
-                store: StoreKind::WindowsNative,
+            Backend::Windows(s) => Self {
+                store: Some(StoreKind::WindowsNative),
                 secret_service: None,
                 linux_keyutils: None,
                 apple_native: None,
                 windows_native: Some(s),
+                policy,
+                rotate_every,
+                expires,
+                history,
+                entry: entry_type,
             },
+        };
+
+        if detected {
+            entry.store = None;
         }
+
+        entry
     }
 }
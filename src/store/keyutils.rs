@@ -16,32 +16,52 @@
 // License along with this program. If not, see
 // <https://www.gnu.org/licenses/>.
 
-#[allow(unused)]
-use anyhow::{anyhow, bail, Context, Result};
-use secrecy::SecretString;
+use secrecy::{SecretSlice, SecretString};
 use serde::{Deserialize, Serialize};
 
-use crate::{keyring, store::StoreExt};
+use crate::{error::Error, keyring, store::StoreExt};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
 pub struct KeyutilsStore {
     pub service: String,
     pub user: String,
 }
 
 impl StoreExt for KeyutilsStore {
-    fn read(&self) -> Result<SecretString> {
+    fn exists(&self) -> Result<bool, Error> {
+        let _lock = keyring::lock();
+        self.init()?;
+        keyring::exists(&self.service, &self.user)
+    }
+
+    fn read(&self) -> Result<SecretString, Error> {
+        let _lock = keyring::lock();
         self.init()?;
         keyring::read(&self.service, &self.user)
     }
 
-    fn write(&self, secret: SecretString) -> Result<()> {
+    fn write(&self, secret: SecretString) -> Result<(), Error> {
+        let _lock = keyring::lock();
         self.init()?;
         keyring::write(&self.service, &self.user, secret)
     }
 
-    fn remove(&self) -> Result<bool> {
+    fn read_secret(&self) -> Result<SecretSlice<u8>, Error> {
+        let _lock = keyring::lock();
+        self.init()?;
+        keyring::read_secret(&self.service, &self.user)
+    }
+
+    fn write_secret(&self, secret: SecretSlice<u8>) -> Result<(), Error> {
+        let _lock = keyring::lock();
+        self.init()?;
+        keyring::write_secret(&self.service, &self.user, secret)
+    }
+
+    fn remove(&self) -> Result<bool, Error> {
+        let _lock = keyring::lock();
         self.init()?;
         keyring::remove(&self.service, &self.user)
     }
@@ -50,22 +70,21 @@ impl StoreExt for KeyutilsStore {
 impl KeyutilsStore {
     #[cfg(target_os = "linux")]
     #[cfg(feature = "keyutils")]
-    fn init(&self) -> Result<()> {
+    fn init(&self) -> Result<(), Error> {
         let store = linux_keyutils_keyring_store::Store::new()
-            .map_err(|err| anyhow!("{err}"))
-            .context("Cannot create Linux keyutils store")?;
+            .map_err(|err| keyring::unavailable("Linux keyutils", err))?;
         keyring_core::set_default_store(store);
         Ok(())
     }
 
     #[cfg(target_os = "linux")]
     #[cfg(not(feature = "keyutils"))]
-    fn init(&self) -> Result<()> {
-        bail!("Feature `keyutils` is missing");
+    fn init(&self) -> Result<(), Error> {
+        Err(Error::feature_missing("keyutils"))
     }
 
     #[cfg(not(target_os = "linux"))]
-    fn init(&self) -> Result<()> {
-        bail!("Feature `keyutils` is not available on this platform");
+    fn init(&self) -> Result<(), Error> {
+        Err(Error::platform_unsupported("Linux keyutils"))
     }
 }
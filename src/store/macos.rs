@@ -16,32 +16,52 @@
 // License along with this program. If not, see
 // <https://www.gnu.org/licenses/>.
 
-#[allow(unused)]
-use anyhow::{anyhow, bail, Context, Result};
-use secrecy::SecretString;
+use secrecy::{SecretSlice, SecretString};
 use serde::{Deserialize, Serialize};
 
-use crate::{keyring, store::StoreExt};
+use crate::{error::Error, keyring, store::StoreExt};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
 pub struct MacosStore {
     pub service: String,
     pub user: String,
 }
 
 impl StoreExt for MacosStore {
-    fn read(&self) -> Result<SecretString> {
+    fn exists(&self) -> Result<bool, Error> {
+        let _lock = keyring::lock();
+        self.init()?;
+        keyring::exists(&self.service, &self.user)
+    }
+
+    fn read(&self) -> Result<SecretString, Error> {
+        let _lock = keyring::lock();
         self.init()?;
         keyring::read(&self.service, &self.user)
     }
 
-    fn write(&self, secret: SecretString) -> Result<()> {
+    fn write(&self, secret: SecretString) -> Result<(), Error> {
+        let _lock = keyring::lock();
         self.init()?;
         keyring::write(&self.service, &self.user, secret)
     }
 
-    fn remove(&self) -> Result<bool> {
+    fn read_secret(&self) -> Result<SecretSlice<u8>, Error> {
+        let _lock = keyring::lock();
+        self.init()?;
+        keyring::read_secret(&self.service, &self.user)
+    }
+
+    fn write_secret(&self, secret: SecretSlice<u8>) -> Result<(), Error> {
+        let _lock = keyring::lock();
+        self.init()?;
+        keyring::write_secret(&self.service, &self.user, secret)
+    }
+
+    fn remove(&self) -> Result<bool, Error> {
+        let _lock = keyring::lock();
         self.init()?;
         keyring::remove(&self.service, &self.user)
     }
@@ -50,22 +70,21 @@ impl StoreExt for MacosStore {
 impl MacosStore {
     #[cfg(target_os = "macos")]
     #[cfg(feature = "apple-keychain")]
-    fn init(&self) -> Result<()> {
+    fn init(&self) -> Result<(), Error> {
         let store = apple_native_keyring_store::keychain::Store::new()
-            .map_err(|err| anyhow!("{err}"))
-            .context("Cannot create Apple Keychain store")?;
+            .map_err(|err| keyring::unavailable("Apple Keychain", err))?;
         keyring_core::set_default_store(store);
         Ok(())
     }
 
     #[cfg(target_os = "macos")]
     #[cfg(not(feature = "apple-keychain"))]
-    fn init(&self) -> Result<()> {
-        bail!("Feature `apple-keychain` is missing");
+    fn init(&self) -> Result<(), Error> {
+        Err(Error::feature_missing("apple-keychain"))
     }
 
     #[cfg(not(target_os = "macos"))]
-    fn init(&self) -> Result<()> {
-        bail!("Feature `apple-keychain` is not available on this platform");
+    fn init(&self) -> Result<(), Error> {
+        Err(Error::platform_unsupported("Apple Keychain"))
     }
 }
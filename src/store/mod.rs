@@ -16,13 +16,21 @@
 // License along with this program. If not, see
 // <https://www.gnu.org/licenses/>.
 
-pub mod de;
+#[path = "async.rs"]
+mod async_store;
+pub(crate) mod de;
 pub mod keyutils;
 pub mod macos;
+mod pool;
 #[path = "secret-service.rs"]
 pub mod secret_service;
+#[allow(clippy::module_inception)]
 mod store;
 pub mod windows;
 
 #[doc(inline)]
-pub use self::store::*;
+pub use self::{
+    async_store::*,
+    de::{EntryType, StoreKind},
+    store::*,
+};
@@ -0,0 +1,164 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+//! A small thread pool running blocking keyring operations on behalf
+//! of asynchronous callers.
+//!
+//! Futures returned by [`spawn`] are runtime-agnostic: they are woken
+//! by the worker thread once the operation completes.
+
+use std::{
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::{
+        mpsc::{self, Sender},
+        Arc, Mutex, OnceLock,
+    },
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+use log::debug;
+
+use crate::error::{Error, ErrorKind};
+
+/// The number of worker threads.
+const WORKERS: usize = 4;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+static POOL: OnceLock<Mutex<Sender<Job>>> = OnceLock::new();
+
+fn pool() -> &'static Mutex<Sender<Job>> {
+    POOL.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<Job>();
+        let rx = Arc::new(Mutex::new(rx));
+
+        for i in 0..WORKERS {
+            let rx = rx.clone();
+
+            thread::Builder::new()
+                .name(format!("mimosa-blocking-{i}"))
+                .spawn(move || loop {
+                    let job = rx
+                        .lock()
+                        .expect("pool receiver should not be poisoned")
+                        .recv();
+
+                    match job {
+                        // a panicking job drops its sender, which
+                        // resolves its future with an error
+                        Ok(job) => drop(panic::catch_unwind(AssertUnwindSafe(job))),
+                        Err(_) => break,
+                    }
+                })
+                .expect("should spawn blocking pool thread");
+        }
+
+        debug!("blocking pool started with {WORKERS} workers");
+        Mutex::new(tx)
+    })
+}
+
+/// Runs the given blocking operation on the pool.
+pub(crate) fn spawn<T, F>(op: F) -> Blocking<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, Error> + Send + 'static,
+{
+    let slot = Arc::new(Mutex::new(Slot {
+        output: None,
+        waker: None,
+        done: false,
+    }));
+
+    let sender = Completion(slot.clone());
+
+    let job: Job = Box::new(move || {
+        let output = op();
+        sender.complete(Some(output));
+    });
+
+    let sent = pool()
+        .lock()
+        .expect("pool sender should not be poisoned")
+        .send(job);
+
+    if let Err(mpsc::SendError(job)) = sent {
+        // the pool is gone, run the operation in place
+        job();
+    }
+
+    Blocking(slot)
+}
+
+struct Slot<T> {
+    output: Option<Result<T, Error>>,
+    waker: Option<Waker>,
+    done: bool,
+}
+
+/// Completes the future on drop, so that panicking operations do
+/// not leave it pending forever.
+struct Completion<T>(Arc<Mutex<Slot<T>>>);
+
+impl<T> Completion<T> {
+    fn complete(&self, output: Option<Result<T, Error>>) {
+        let mut slot = self.0.lock().expect("blocking slot should not be poisoned");
+
+        if slot.done {
+            return;
+        }
+
+        slot.done = true;
+        slot.output = output;
+
+        if let Some(waker) = slot.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Drop for Completion<T> {
+    fn drop(&mut self) {
+        self.complete(None);
+    }
+}
+
+/// The future of a blocking operation run on the pool.
+pub(crate) struct Blocking<T>(Arc<Mutex<Slot<T>>>);
+
+impl<T> Future for Blocking<T> {
+    type Output = Result<T, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.0.lock().expect("blocking slot should not be poisoned");
+
+        if !slot.done {
+            slot.waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+
+        Poll::Ready(slot.output.take().unwrap_or_else(|| {
+            Err(Error::new(
+                ErrorKind::Other,
+                "Blocking keyring operation panicked",
+            ))
+        }))
+    }
+}
@@ -16,15 +16,14 @@
 // License along with this program. If not, see
 // <https://www.gnu.org/licenses/>.
 
-#[allow(unused)]
-use anyhow::{anyhow, bail, Context, Result};
-use secrecy::SecretString;
+use secrecy::{SecretSlice, SecretString};
 use serde::{Deserialize, Serialize};
 
-use crate::{keyring, store::StoreExt};
+use crate::{error::Error, keyring, store::StoreExt};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
 pub struct SecretServiceStore {
     pub service: String,
     pub user: String,
@@ -34,30 +33,67 @@ pub struct SecretServiceStore {
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
 pub enum Flavour {
     Dbus,
     Zbus,
 }
 
 impl StoreExt for SecretServiceStore {
-    fn read(&self) -> Result<SecretString> {
+    fn exists(&self) -> Result<bool, Error> {
+        let _lock = keyring::lock();
+        self.init()?;
+        keyring::exists(&self.service, &self.user)
+    }
+
+    fn read(&self) -> Result<SecretString, Error> {
+        let _lock = keyring::lock();
         self.init()?;
         keyring::read(&self.service, &self.user)
     }
 
-    fn write(&self, secret: SecretString) -> Result<()> {
+    fn write(&self, secret: SecretString) -> Result<(), Error> {
+        let _lock = keyring::lock();
         self.init()?;
         keyring::write(&self.service, &self.user, secret)
     }
 
-    fn remove(&self) -> Result<bool> {
+    fn read_secret(&self) -> Result<SecretSlice<u8>, Error> {
+        let _lock = keyring::lock();
+        self.init()?;
+        keyring::read_secret(&self.service, &self.user)
+    }
+
+    fn write_secret(&self, secret: SecretSlice<u8>) -> Result<(), Error> {
+        let _lock = keyring::lock();
+        self.init()?;
+        keyring::write_secret(&self.service, &self.user, secret)
+    }
+
+    fn remove(&self) -> Result<bool, Error> {
+        let _lock = keyring::lock();
         self.init()?;
         keyring::remove(&self.service, &self.user)
     }
 }
 
 impl SecretServiceStore {
-    fn init(&self) -> Result<()> {
+    /// Returns `true` when the store uses the zbus implementation,
+    /// which can be used natively asynchronously.
+    pub fn is_zbus(&self) -> bool {
+        let zbus = cfg!(all(
+            any(target_os = "linux", target_os = "freebsd"),
+            feature = "zbus-secret-service"
+        ));
+
+        match &self.flavour {
+            Some(Flavour::Zbus) => zbus,
+            Some(Flavour::Dbus) => false,
+            None => zbus && !cfg!(feature = "dbus-secret-service"),
+        }
+    }
+
+    fn init(&self) -> Result<(), Error> {
         match &self.flavour {
             Some(Flavour::Dbus) => self.init_dbus(),
             Some(Flavour::Zbus) => self.init_zbus(),
@@ -67,10 +103,9 @@ impl SecretServiceStore {
 
     #[cfg(any(target_os = "linux", target_os = "freebsd"))]
     #[cfg(feature = "dbus-secret-service")]
-    fn init_dbus(&self) -> Result<()> {
+    fn init_dbus(&self) -> Result<(), Error> {
         let store = dbus_secret_service_keyring_store::Store::new()
-            .map_err(|err| anyhow!("{err}"))
-            .context("Cannot create D-Bus Secret Service store")?;
+            .map_err(|err| keyring::unavailable("D-Bus Secret Service", err))?;
 
         keyring_core::set_default_store(store);
 
@@ -79,46 +114,45 @@ impl SecretServiceStore {
 
     #[cfg(any(target_os = "linux", target_os = "freebsd"))]
     #[cfg(not(feature = "dbus-secret-service"))]
-    fn init_dbus(&self) -> Result<()> {
-        bail!("Feature `dbus-secret-service` is missing");
+    fn init_dbus(&self) -> Result<(), Error> {
+        Err(Error::feature_missing("dbus-secret-service"))
     }
 
     #[cfg(not(any(target_os = "linux", target_os = "freebsd")))]
-    fn init_dbus(&self) -> Result<()> {
-        bail!("Secret Service is not available on this platform");
+    fn init_dbus(&self) -> Result<(), Error> {
+        Err(Error::platform_unsupported("Secret Service"))
     }
 
     #[cfg(any(target_os = "linux", target_os = "freebsd"))]
     #[cfg(feature = "zbus-secret-service")]
-    fn init_zbus(&self) -> Result<()> {
+    fn init_zbus(&self) -> Result<(), Error> {
         let store = zbus_secret_service_keyring_store::Store::new()
-            .map_err(|err| anyhow!("{err}"))
-            .context("Cannot create zbus Secret Service store")?;
+            .map_err(|err| keyring::unavailable("zbus Secret Service", err))?;
         keyring_core::set_default_store(store);
         Ok(())
     }
 
     #[cfg(any(target_os = "linux", target_os = "freebsd"))]
     #[cfg(not(feature = "zbus-secret-service"))]
-    fn init_zbus(&self) -> Result<()> {
-        bail!("Feature `zbus-secret-service` is missing");
+    fn init_zbus(&self) -> Result<(), Error> {
+        Err(Error::feature_missing("zbus-secret-service"))
     }
 
     #[cfg(not(any(target_os = "linux", target_os = "freebsd")))]
-    fn init_zbus(&self) -> Result<()> {
-        bail!("Secret Service is not available on this platform");
+    fn init_zbus(&self) -> Result<(), Error> {
+        Err(Error::platform_unsupported("Secret Service"))
     }
 
     #[cfg(any(target_os = "linux", target_os = "freebsd"))]
     #[cfg(feature = "dbus-secret-service")]
-    fn init_default(&self) -> Result<()> {
+    fn init_default(&self) -> Result<(), Error> {
         self.init_dbus()
     }
 
     #[cfg(any(target_os = "linux", target_os = "freebsd"))]
     #[cfg(not(feature = "dbus-secret-service"))]
     #[cfg(feature = "zbus-secret-service")]
-    fn init_default(&self) -> Result<()> {
+    fn init_default(&self) -> Result<(), Error> {
         self.init_zbus()
     }
 
@@ -130,7 +164,182 @@ impl SecretServiceStore {
         any(target_os = "linux", target_os = "freebsd"),
         feature = "zbus-secret-service"
     )))]
-    fn init_default(&self) -> Result<()> {
-        bail!("no Secret Service implementation available");
+    fn init_default(&self) -> Result<(), Error> {
+        Err(Error::feature_missing("dbus-secret-service"))
+    }
+}
+
+/// Natively asynchronous access to the Secret Service, using the
+/// same items as the zbus keyring store: items are searched by
+/// `service` and `username` attributes, and created in the default
+/// collection.
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+#[cfg(feature = "zbus-secret-service")]
+pub(crate) mod native {
+    use std::collections::HashMap;
+
+    use secrecy::{ExposeSecret, SecretSlice, SecretString};
+    use secret_service::{EncryptionType, Item, SecretService};
+    use zeroize::Zeroize;
+
+    use crate::error::{Error, ErrorKind};
+
+    use super::SecretServiceStore;
+
+    /// The content type of secrets, as set by the keyring stores
+    /// when creating items.
+    const CONTENT_TYPE: &str = "application/octet-stream";
+
+    fn error(message: &str, err: secret_service::Error) -> Error {
+        use secret_service::Error::*;
+
+        let kind = match &err {
+            Locked | NoResult | Prompt => ErrorKind::AccessDenied,
+            _ => ErrorKind::BackendUnavailable,
+        };
+
+        Error::new(kind, message).with_source(err)
+    }
+
+    fn attributes(store: &SecretServiceStore) -> HashMap<&str, &str> {
+        HashMap::from([
+            ("service", store.service.as_str()),
+            ("username", store.user.as_str()),
+        ])
+    }
+
+    async fn connect() -> Result<SecretService<'static>, Error> {
+        SecretService::connect(EncryptionType::Dh)
+            .await
+            .map_err(|err| error("Cannot connect to Secret Service", err))
+    }
+
+    /// Returns the unique item matching the store, if any.
+    async fn find<'a>(
+        ss: &'a SecretService<'_>,
+        store: &SecretServiceStore,
+    ) -> Result<Option<Item<'a>>, Error> {
+        let search = ss
+            .search_items(attributes(store))
+            .await
+            .map_err(|err| error("Cannot search Secret Service items", err))?;
+
+        if !search.locked.is_empty() {
+            let items: Vec<&Item> = search.locked.iter().collect();
+            ss.unlock_all(&items)
+                .await
+                .map_err(|err| error("Cannot unlock Secret Service items", err))?;
+        }
+
+        let mut items = search.unlocked.into_iter().chain(search.locked);
+
+        match (items.next(), items.next()) {
+            (None, _) => Ok(None),
+            (Some(item), None) => Ok(Some(item)),
+            (Some(_), Some(_)) => {
+                let message = "Ambiguous Secret Service items, several match the store";
+                Err(Error::new(ErrorKind::Other, message))
+            }
+        }
+    }
+
+    pub(crate) async fn exists(store: &SecretServiceStore) -> Result<bool, Error> {
+        let ss = connect().await?;
+        Ok(find(&ss, store).await?.is_some())
+    }
+
+    pub(crate) async fn read_secret(store: &SecretServiceStore) -> Result<SecretSlice<u8>, Error> {
+        let ss = connect().await?;
+
+        let Some(item) = find(&ss, store).await? else {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                "Secret not found in keyring",
+            ));
+        };
+
+        let secret = item
+            .get_secret()
+            .await
+            .map_err(|err| error("Cannot read secret from keyring", err))?;
+
+        Ok(SecretSlice::from(secret))
+    }
+
+    pub(crate) async fn read(store: &SecretServiceStore) -> Result<SecretString, Error> {
+        let secret = read_secret(store).await?;
+
+        // the error holds the secret bytes, so it is zeroized rather
+        // than kept as source
+        let password = String::from_utf8(secret.expose_secret().to_vec()).map_err(|err| {
+            err.into_bytes().zeroize();
+            Error::new(
+                ErrorKind::Other,
+                "Cannot read password from keyring: invalid UTF-8",
+            )
+        })?;
+
+        Ok(SecretString::from(password))
+    }
+
+    pub(crate) async fn write(
+        store: &SecretServiceStore,
+        secret: SecretString,
+    ) -> Result<(), Error> {
+        let secret = SecretSlice::from(secret.expose_secret().as_bytes().to_vec());
+        write_secret(store, secret).await
+    }
+
+    pub(crate) async fn write_secret(
+        store: &SecretServiceStore,
+        secret: SecretSlice<u8>,
+    ) -> Result<(), Error> {
+        let ss = connect().await?;
+
+        if let Some(item) = find(&ss, store).await? {
+            return item
+                .set_secret(secret.expose_secret(), CONTENT_TYPE)
+                .await
+                .map_err(|err| error("Cannot write secret to keyring", err));
+        }
+
+        let collection = ss
+            .get_default_collection()
+            .await
+            .map_err(|err| error("Cannot get default Secret Service collection", err))?;
+
+        collection
+            .ensure_unlocked()
+            .await
+            .map_err(|err| error("Cannot unlock default Secret Service collection", err))?;
+
+        let label = format!("keyring:{}@{}", store.user, store.service);
+
+        collection
+            .create_item(
+                &label,
+                attributes(store),
+                secret.expose_secret(),
+                true,
+                CONTENT_TYPE,
+            )
+            .await
+            .map_err(|err| error("Cannot write secret to keyring", err))?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn remove(store: &SecretServiceStore) -> Result<bool, Error> {
+        let ss = connect().await?;
+
+        let Some(item) = find(&ss, store).await? else {
+            return Ok(false);
+        };
+
+        item.delete()
+            .await
+            .map_err(|err| error("Cannot remove password from keyring", err))?;
+
+        Ok(true)
     }
 }
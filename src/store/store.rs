@@ -16,36 +16,429 @@
 // License along with this program. If not, see
 // <https://www.gnu.org/licenses/>.
 
-use anyhow::Result;
-use secrecy::SecretString;
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime},
+};
+
+use log::debug;
+use secrecy::{SecretSlice, SecretString};
 use serde::{Deserialize, Serialize};
 
-use crate::store::{
-    keyutils::KeyutilsStore, macos::MacosStore, secret_service::SecretServiceStore,
-    windows::WindowsStore,
+use crate::{
+    audit::Policy,
+    error::{Error, ErrorKind},
+    store::{
+        keyutils::KeyutilsStore,
+        macos::MacosStore,
+        secret_service::{Flavour, SecretServiceStore},
+        windows::WindowsStore,
+        EntryType, StoreKind,
+    },
+    template,
 };
 
 use super::de;
 
 /// The contract every store must satisfy.
 pub trait StoreExt {
-    fn read(&self) -> Result<SecretString>;
-    fn write(&self, secret: SecretString) -> Result<()>;
-    fn remove(&self) -> Result<bool>;
+    /// Returns `true` when the store has a secret.
+    fn exists(&self) -> Result<bool, Error>;
+    /// Reads the secret as text.
+    fn read(&self) -> Result<SecretString, Error>;
+    /// Writes the given text secret.
+    fn write(&self, secret: SecretString) -> Result<(), Error>;
+    /// Reads the secret as bytes.
+    fn read_secret(&self) -> Result<SecretSlice<u8>, Error>;
+    /// Writes the given bytes secret.
+    fn write_secret(&self, secret: SecretSlice<u8>) -> Result<(), Error>;
+    /// Removes the secret, returning `false` when there was none.
+    fn remove(&self) -> Result<bool, Error>;
 }
 
-/// A store configuration, resolved from the TOML config.
+/// A store, resolved from the TOML config or built with
+/// [`Store::builder`].
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(try_from = "de::Store", into = "de::Store")]
-pub enum Store {
+pub struct Store {
+    /// The keyring backend holding the secret.
+    pub(crate) backend: Backend,
+    /// The password policy checked by `mimosa audit`.
+    pub(crate) policy: Option<Policy>,
+    /// The interval after which the secret should be rotated.
+    pub(crate) rotate_every: Option<Duration>,
+    /// The date after which the secret is considered expired.
+    pub(crate) expires: Option<SystemTime>,
+    /// The number of previous secrets kept when writing a new one.
+    pub(crate) history: usize,
+    /// The type of secret held by the store.
+    pub(crate) entry: EntryType,
+    /// Whether the backend was detected, `store` being omitted.
+    pub(crate) detected: bool,
+}
+
+/// The keyring backend of a store.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum Backend {
     SecretService(SecretServiceStore),
     Keyutils(KeyutilsStore),
     Macos(MacosStore),
     Windows(WindowsStore),
 }
 
+impl Store {
+    /// Returns a builder of a store holding the secret of the given
+    /// keyring service and user.
+    pub fn builder(service: impl Into<String>, user: impl Into<String>) -> StoreBuilder {
+        StoreBuilder::new(service, user)
+    }
+
+    pub fn backend(&self) -> &Backend {
+        &self.backend
+    }
+
+    pub fn policy(&self) -> Option<&Policy> {
+        self.policy.as_ref()
+    }
+
+    pub fn rotate_every(&self) -> Option<Duration> {
+        self.rotate_every
+    }
+
+    pub fn expires(&self) -> Option<SystemTime> {
+        self.expires
+    }
+
+    pub fn history(&self) -> usize {
+        self.history
+    }
+
+    pub fn entry(&self) -> EntryType {
+        self.entry
+    }
+
+    /// Returns `true` when the backend was detected rather than
+    /// explicitly chosen.
+    pub fn is_detected(&self) -> bool {
+        self.detected
+    }
+
+    /// Returns a copy of the store with `{name}` placeholders of its
+    /// service and user replaced by the given variables.
+    pub fn render(&self, vars: &HashMap<String, String>) -> Result<Self, Error> {
+        Ok(Self {
+            backend: self.backend.render(vars)?,
+            ..self.clone()
+        })
+    }
+
+    /// Returns the secret written `n` writes ago, if any.
+    ///
+    /// The most recent previous secret is at position 1.
+    pub fn read_history(&self, n: usize) -> Result<Option<SecretSlice<u8>>, Error> {
+        let backend = self.backend.history(n);
+
+        if backend.exists()? {
+            Ok(Some(backend.read_secret()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Removes the previous secrets, returning how many there were.
+    pub fn remove_history(&self) -> Result<usize, Error> {
+        let mut removed = 0;
+
+        for n in 1..=self.history {
+            if self.backend.history(n).remove()? {
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+
+    /// Moves the current secret into the history, shifting previous
+    /// secrets and dropping the ones exceeding the history size.
+    fn archive(&self) -> Result<(), Error> {
+        if self.history == 0 {
+            return Ok(());
+        }
+
+        debug!("archive current secret, keeping {} previous", self.history);
+
+        for n in (1..self.history).rev() {
+            let next = self.backend.history(n + 1);
+
+            match self.read_history(n)? {
+                Some(secret) => next.write_secret(secret)?,
+                None => {
+                    next.remove()?;
+                }
+            }
+        }
+
+        let first = self.backend.history(1);
+
+        if self.backend.exists()? {
+            first.write_secret(self.backend.read_secret()?)
+        } else {
+            first.remove().map(|_| ())
+        }
+    }
+}
+
+/// Builder of a [`Store`], for programs resolving credentials
+/// without TOML configuration.
+///
+/// ```no_run
+/// use mimosa::store::{Store, StoreExt, StoreKind};
+///
+/// let store = Store::builder("my-app", "me")
+///     .kind(StoreKind::SecretService)
+///     .history(3)
+///     .build()?;
+///
+/// let password = store.read()?;
+/// # Ok::<(), mimosa::error::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct StoreBuilder {
+    service: String,
+    user: String,
+    kind: Option<StoreKind>,
+    flavour: Option<Flavour>,
+    policy: Option<Policy>,
+    rotate_every: Option<Duration>,
+    expires: Option<SystemTime>,
+    history: usize,
+    entry: EntryType,
+}
+
+impl StoreBuilder {
+    pub fn new(service: impl Into<String>, user: impl Into<String>) -> Self {
+        Self {
+            service: service.into(),
+            user: user.into(),
+            kind: None,
+            flavour: None,
+            policy: None,
+            rotate_every: None,
+            expires: None,
+            history: 0,
+            entry: EntryType::default(),
+        }
+    }
+
+    /// Sets the backend kind. When unset, the backend is detected the
+    /// same way as for stores omitting `store` in the config.
+    pub fn kind(mut self, kind: StoreKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Sets the Secret Service implementation, ignored by other
+    /// backends.
+    pub fn flavour(mut self, flavour: Flavour) -> Self {
+        self.flavour = Some(flavour);
+        self
+    }
+
+    pub fn policy(mut self, policy: Policy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    pub fn rotate_every(mut self, interval: Duration) -> Self {
+        self.rotate_every = Some(interval);
+        self
+    }
+
+    pub fn expires(mut self, date: SystemTime) -> Self {
+        self.expires = Some(date);
+        self
+    }
+
+    pub fn history(mut self, size: usize) -> Self {
+        self.history = size;
+        self
+    }
+
+    pub fn entry(mut self, entry: EntryType) -> Self {
+        self.entry = entry;
+        self
+    }
+
+    pub fn build(self) -> Result<Store, Error> {
+        let detected = self.kind.is_none();
+
+        let kind = match self.kind {
+            Some(kind) => kind,
+            None => StoreKind::detect(|_| true).ok_or_else(|| {
+                let message = "No keyring backend available on this platform";
+                Error::new(ErrorKind::PlatformUnsupported, message)
+            })?,
+        };
+
+        if let Some(feature) = kind.missing_feature() {
+            return Err(Error::feature_missing(feature));
+        }
+
+        let (service, user) = (self.service, self.user);
+
+        let backend = match kind {
+            StoreKind::SecretService => Backend::SecretService(SecretServiceStore {
+                service,
+                user,
+                flavour: self.flavour,
+            }),
+            StoreKind::LinuxKeyutils => Backend::Keyutils(KeyutilsStore { service, user }),
+            StoreKind::AppleNative => Backend::Macos(MacosStore { service, user }),
+            StoreKind::WindowsNative => Backend::Windows(WindowsStore { service, user }),
+        };
+
+        Ok(Store {
+            backend,
+            policy: self.policy,
+            rotate_every: self.rotate_every,
+            expires: self.expires,
+            history: self.history,
+            entry: self.entry,
+            detected,
+        })
+    }
+}
+
 impl StoreExt for Store {
-    fn read(&self) -> Result<SecretString> {
+    fn exists(&self) -> Result<bool, Error> {
+        self.backend.exists()
+    }
+
+    fn read(&self) -> Result<SecretString, Error> {
+        self.backend.read()
+    }
+
+    fn write(&self, secret: SecretString) -> Result<(), Error> {
+        self.archive()?;
+        self.backend.write(secret)
+    }
+
+    fn read_secret(&self) -> Result<SecretSlice<u8>, Error> {
+        self.backend.read_secret()
+    }
+
+    fn write_secret(&self, secret: SecretSlice<u8>) -> Result<(), Error> {
+        self.archive()?;
+        self.backend.write_secret(secret)
+    }
+
+    /// Removes the secret along with its history.
+    fn remove(&self) -> Result<bool, Error> {
+        let removed = self.backend.remove()?;
+        self.remove_history()?;
+        Ok(removed)
+    }
+}
+
+impl Backend {
+    /// Returns the kind of the backend.
+    pub fn kind(&self) -> de::StoreKind {
+        match self {
+            Self::SecretService(_) => de::StoreKind::SecretService,
+            Self::Keyutils(_) => de::StoreKind::LinuxKeyutils,
+            Self::Macos(_) => de::StoreKind::AppleNative,
+            Self::Windows(_) => de::StoreKind::WindowsNative,
+        }
+    }
+
+    /// Returns the keyring service name.
+    pub fn service(&self) -> &str {
+        match self {
+            Self::SecretService(s) => &s.service,
+            Self::Keyutils(s) => &s.service,
+            Self::Macos(s) => &s.service,
+            Self::Windows(s) => &s.service,
+        }
+    }
+
+    /// Returns the keyring user name.
+    pub fn user(&self) -> &str {
+        match self {
+            Self::SecretService(s) => &s.user,
+            Self::Keyutils(s) => &s.user,
+            Self::Macos(s) => &s.user,
+            Self::Windows(s) => &s.user,
+        }
+    }
+
+    /// Replaces `{name}` placeholders of the service and the user.
+    pub fn render(&self, vars: &HashMap<String, String>) -> Result<Self, Error> {
+        let render = |template: &str| {
+            template::render(template, vars)
+                .map_err(|err| Error::config("Cannot render store template").with_source(err))
+        };
+
+        Ok(match self {
+            Self::SecretService(s) => Self::SecretService(SecretServiceStore {
+                service: render(&s.service)?,
+                user: render(&s.user)?,
+                ..s.clone()
+            }),
+            Self::Keyutils(s) => Self::Keyutils(KeyutilsStore {
+                service: render(&s.service)?,
+                user: render(&s.user)?,
+            }),
+            Self::Macos(s) => Self::Macos(MacosStore {
+                service: render(&s.service)?,
+                user: render(&s.user)?,
+            }),
+            Self::Windows(s) => Self::Windows(WindowsStore {
+                service: render(&s.service)?,
+                user: render(&s.user)?,
+            }),
+        })
+    }
+
+    /// Returns the backend holding the secret written `n` writes ago.
+    ///
+    /// History entries share the service of the current secret, with
+    /// the user suffixed by `~<n>`.
+    pub fn history(&self, n: usize) -> Self {
+        let user = |user: &str| format!("{user}~{n}");
+
+        match self {
+            Self::SecretService(s) => Self::SecretService(SecretServiceStore {
+                user: user(&s.user),
+                ..s.clone()
+            }),
+            Self::Keyutils(s) => Self::Keyutils(KeyutilsStore {
+                user: user(&s.user),
+                ..s.clone()
+            }),
+            Self::Macos(s) => Self::Macos(MacosStore {
+                user: user(&s.user),
+                ..s.clone()
+            }),
+            Self::Windows(s) => Self::Windows(WindowsStore {
+                user: user(&s.user),
+                ..s.clone()
+            }),
+        }
+    }
+}
+
+impl StoreExt for Backend {
+    fn exists(&self) -> Result<bool, Error> {
+        match self {
+            Self::SecretService(s) => s.exists(),
+            Self::Keyutils(s) => s.exists(),
+            Self::Macos(s) => s.exists(),
+            Self::Windows(s) => s.exists(),
+        }
+    }
+
+    fn read(&self) -> Result<SecretString, Error> {
         match self {
             Self::SecretService(s) => s.read(),
             Self::Keyutils(s) => s.read(),
@@ -54,7 +447,7 @@ impl StoreExt for Store {
         }
     }
 
-    fn write(&self, secret: SecretString) -> Result<()> {
+    fn write(&self, secret: SecretString) -> Result<(), Error> {
         match self {
             Self::SecretService(s) => s.write(secret),
             Self::Keyutils(s) => s.write(secret),
@@ -63,7 +456,25 @@ impl StoreExt for Store {
         }
     }
 
-    fn remove(&self) -> Result<bool> {
+    fn read_secret(&self) -> Result<SecretSlice<u8>, Error> {
+        match self {
+            Self::SecretService(s) => s.read_secret(),
+            Self::Keyutils(s) => s.read_secret(),
+            Self::Macos(s) => s.read_secret(),
+            Self::Windows(s) => s.read_secret(),
+        }
+    }
+
+    fn write_secret(&self, secret: SecretSlice<u8>) -> Result<(), Error> {
+        match self {
+            Self::SecretService(s) => s.write_secret(secret),
+            Self::Keyutils(s) => s.write_secret(secret),
+            Self::Macos(s) => s.write_secret(secret),
+            Self::Windows(s) => s.write_secret(secret),
+        }
+    }
+
+    fn remove(&self) -> Result<bool, Error> {
         match self {
             Self::SecretService(s) => s.remove(),
             Self::Keyutils(s) => s.remove(),
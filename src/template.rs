@@ -0,0 +1,81 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use std::{collections::HashMap, env};
+
+use anyhow::{bail, Result};
+
+/// Runtime variables used to fill store templates.
+pub type Vars = HashMap<String, String>;
+
+/// Replaces `{name}` placeholders of the given template.
+///
+/// Values are taken from the given variables first, then from the
+/// environment variable of the same name. Use `{{` and `}}` to
+/// escape braces.
+pub fn render(template: &str, vars: &Vars) -> Result<String> {
+    let mut output = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                output.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                output.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => bail!("Unclosed placeholder in template {template:?}"),
+                    }
+                }
+
+                let name = name.trim();
+
+                match vars.get(name) {
+                    Some(val) => output.push_str(val),
+                    None => match env::var(name) {
+                        Ok(val) => output.push_str(&val),
+                        Err(_) => {
+                            bail!("Missing variable {name:?}, set it with `--var {name}=<value>`")
+                        }
+                    },
+                }
+            }
+            c => output.push(c),
+        }
+    }
+
+    Ok(output)
+}
+
+/// Clap value parser for `KEY=VALUE` variables.
+pub fn var_parser(var: &str) -> Result<(String, String), String> {
+    match var.split_once('=') {
+        Some((key, val)) if !key.trim().is_empty() => Ok((key.trim().to_owned(), val.to_owned())),
+        _ => Err(format!("invalid variable {var:?}, expected KEY=VALUE")),
+    }
+}
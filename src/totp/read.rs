@@ -0,0 +1,87 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use std::fmt;
+
+use anyhow::Result;
+use clap::Parser;
+use pimalaya_toolbox::terminal::printer::Printer;
+use secrecy::ExposeSecret;
+use serde::Serialize;
+
+use crate::{
+    config::Config,
+    store::StoreExt,
+    totp::{Algorithm, Totp, TotpCode},
+};
+
+/// Generate the current one-time code from a TOTP seed.
+///
+/// The store must contain either an otpauth:// URI or a base32 seed.
+/// Options given as arguments take precedence over the ones from the
+/// URI.
+#[derive(Parser, Debug)]
+pub struct ReadTotpCommand {
+    /// Name of the store in the configuration file.
+    pub store: String,
+
+    /// Number of digits of the code.
+    #[arg(long, short, value_name = "N")]
+    pub digits: Option<u32>,
+
+    /// Validity period of the code, in seconds.
+    #[arg(long, short, value_name = "SECS")]
+    pub period: Option<u64>,
+
+    /// HMAC algorithm used to generate the code.
+    #[arg(long, short, value_enum, value_name = "ALGORITHM")]
+    pub algorithm: Option<Algorithm>,
+}
+
+impl ReadTotpCommand {
+    pub fn execute(self, printer: &mut impl Printer, config: &Config) -> Result<()> {
+        let seed = config.get_store(&self.store)?.read()?;
+        let mut totp = Totp::parse(seed.expose_secret())?;
+
+        if let Some(digits) = self.digits {
+            totp.digits = digits;
+        }
+
+        if let Some(period) = self.period {
+            totp.period = period;
+        }
+
+        if let Some(algorithm) = self.algorithm {
+            totp.algorithm = algorithm;
+        }
+
+        totp.validate()?;
+
+        printer.out(Code(totp.generate()?))
+    }
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct Code(TotpCode);
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.code)
+    }
+}
@@ -0,0 +1,312 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use data_encoding::BASE32_NOPAD;
+use hmac::{digest::KeyInit, Hmac, Mac};
+use secrecy::{ExposeSecret, SecretSlice};
+use serde::Serialize;
+use sha1::Sha1;
+use sha2::{Sha256, Sha512};
+use url::Url;
+use zeroize::Zeroizing;
+
+/// The HMAC algorithm used to generate codes.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum Algorithm {
+    #[default]
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl Algorithm {
+    fn parse(algorithm: &str) -> Result<Self> {
+        match algorithm.to_ascii_uppercase().as_str() {
+            "SHA1" => Ok(Self::Sha1),
+            "SHA256" => Ok(Self::Sha256),
+            "SHA512" => Ok(Self::Sha512),
+            _ => bail!("Invalid TOTP algorithm {algorithm:?}"),
+        }
+    }
+}
+
+/// A time-based one-time password generator, as defined in RFC 6238.
+#[derive(Debug)]
+pub struct Totp {
+    secret: SecretSlice<u8>,
+    pub digits: u32,
+    pub period: u64,
+    pub algorithm: Algorithm,
+}
+
+/// A code generated by [`Totp`], with its remaining validity.
+#[derive(Clone, Debug, Serialize)]
+pub struct TotpCode {
+    pub code: String,
+    pub remaining: u64,
+    pub period: u64,
+}
+
+impl Totp {
+    pub const DEFAULT_DIGITS: u32 = 6;
+    pub const DEFAULT_PERIOD: u64 = 30;
+
+    /// Parses either an `otpauth://totp/` URI or a base32 seed.
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+
+        if input.starts_with("otpauth://") {
+            Self::parse_uri(input)
+        } else {
+            Ok(Self {
+                secret: decode_base32(input)?,
+                digits: Self::DEFAULT_DIGITS,
+                period: Self::DEFAULT_PERIOD,
+                algorithm: Algorithm::default(),
+            })
+        }
+    }
+
+    fn parse_uri(uri: &str) -> Result<Self> {
+        let uri = Url::parse(uri).context("Invalid otpauth URI")?;
+
+        if uri.host_str() != Some("totp") {
+            bail!("Invalid otpauth URI: only `totp` type is supported");
+        }
+
+        let mut secret = None;
+        let mut digits = Self::DEFAULT_DIGITS;
+        let mut period = Self::DEFAULT_PERIOD;
+        let mut algorithm = Algorithm::default();
+
+        for (key, val) in uri.query_pairs() {
+            match key.as_ref() {
+                "secret" => secret = Some(decode_base32(&val)?),
+                "digits" => digits = val.parse().context("Invalid otpauth digits")?,
+                "period" => period = val.parse().context("Invalid otpauth period")?,
+                "algorithm" => algorithm = Algorithm::parse(&val)?,
+                _ => (),
+            }
+        }
+
+        let Some(secret) = secret else {
+            bail!("Invalid otpauth URI: missing secret");
+        };
+
+        let totp = Self {
+            secret,
+            digits,
+            period,
+            algorithm,
+        };
+
+        totp.validate()?;
+        Ok(totp)
+    }
+
+    /// Ensures digits and period can produce valid codes.
+    pub fn validate(&self) -> Result<()> {
+        if !(1..=10).contains(&self.digits) {
+            bail!("Invalid TOTP digits {}, expected 1 to 10", self.digits);
+        }
+
+        if self.period == 0 {
+            bail!("Invalid TOTP period, expected a positive number of seconds");
+        }
+
+        Ok(())
+    }
+
+    /// Generates the code for the given Unix timestamp.
+    pub fn generate_at(&self, timestamp: u64) -> TotpCode {
+        let counter = timestamp / self.period;
+        let key = self.secret.expose_secret();
+        let msg = counter.to_be_bytes();
+
+        let hash = match self.algorithm {
+            Algorithm::Sha1 => hmac::<Hmac<Sha1>>(key, &msg),
+            Algorithm::Sha256 => hmac::<Hmac<Sha256>>(key, &msg),
+            Algorithm::Sha512 => hmac::<Hmac<Sha512>>(key, &msg),
+        };
+
+        // dynamic truncation, see RFC 4226 section 5.3
+        let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+        let bin = u32::from_be_bytes([
+            hash[offset] & 0x7f,
+            hash[offset + 1],
+            hash[offset + 2],
+            hash[offset + 3],
+        ]);
+
+        let code = u64::from(bin) % 10u64.pow(self.digits);
+
+        TotpCode {
+            code: format!("{code:0width$}", width = self.digits as usize),
+            remaining: self.period - timestamp % self.period,
+            period: self.period,
+        }
+    }
+
+    /// Generates the code for the current time.
+    pub fn generate(&self) -> Result<TotpCode> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .context("Cannot get current time")?;
+
+        Ok(self.generate_at(now.as_secs()))
+    }
+}
+
+fn hmac<M: Mac + KeyInit>(key: &[u8], msg: &[u8]) -> Vec<u8> {
+    let mut mac = <M as KeyInit>::new_from_slice(key).expect("HMAC should accept keys of any size");
+    mac.update(msg);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn decode_base32(seed: &str) -> Result<SecretSlice<u8>> {
+    let seed: Zeroizing<String> = Zeroizing::new(
+        seed.chars()
+            .filter(|c| !c.is_whitespace() && *c != '=' && *c != '-')
+            .map(|c| c.to_ascii_uppercase())
+            .collect(),
+    );
+
+    let secret = BASE32_NOPAD
+        .decode(seed.as_bytes())
+        .context("Invalid TOTP seed: expected base32")?;
+
+    Ok(SecretSlice::from(secret))
+}
+
+#[cfg(test)]
+mod tests {
+    use secrecy::{ExposeSecret, SecretSlice};
+
+    use super::{decode_base32, Algorithm, Totp};
+
+    const SHA1_SEED: &[u8] = b"12345678901234567890";
+    const SHA256_SEED: &[u8] = b"12345678901234567890123456789012";
+    const SHA512_SEED: &[u8] = b"1234567890123456789012345678901234567890123456789012345678901234";
+
+    // test vectors from RFC 6238 appendix B
+    const VECTORS: [(u64, &str, &str, &str); 6] = [
+        (59, "94287082", "46119246", "90693936"),
+        (1111111109, "07081804", "68084774", "25091201"),
+        (1111111111, "14050471", "67062674", "99943326"),
+        (1234567890, "89005924", "91819424", "93441116"),
+        (2000000000, "69279037", "90698825", "38618901"),
+        (20000000000, "65353130", "77737706", "47863826"),
+    ];
+
+    fn totp(seed: &[u8], algorithm: Algorithm) -> Totp {
+        Totp {
+            secret: SecretSlice::from(seed.to_vec()),
+            digits: 8,
+            period: 30,
+            algorithm,
+        }
+    }
+
+    #[test]
+    fn rfc6238_vectors() {
+        let sha1 = totp(SHA1_SEED, Algorithm::Sha1);
+        let sha256 = totp(SHA256_SEED, Algorithm::Sha256);
+        let sha512 = totp(SHA512_SEED, Algorithm::Sha512);
+
+        for (timestamp, code1, code256, code512) in VECTORS {
+            assert_eq!(
+                sha1.generate_at(timestamp).code,
+                code1,
+                "SHA1 at {timestamp}"
+            );
+            assert_eq!(
+                sha256.generate_at(timestamp).code,
+                code256,
+                "SHA256 at {timestamp}"
+            );
+            assert_eq!(
+                sha512.generate_at(timestamp).code,
+                code512,
+                "SHA512 at {timestamp}"
+            );
+        }
+    }
+
+    #[test]
+    fn remaining() {
+        let totp = totp(SHA1_SEED, Algorithm::Sha1);
+
+        assert_eq!(totp.generate_at(59).remaining, 1);
+        assert_eq!(totp.generate_at(60).remaining, 30);
+        assert_eq!(totp.generate_at(61).remaining, 29);
+    }
+
+    #[test]
+    fn base32() {
+        let secret = decode_base32("GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ").unwrap();
+        assert_eq!(secret.expose_secret(), SHA1_SEED);
+
+        // padding, grouping and lowercase are tolerated
+        let secret = decode_base32("gezd gnbv-gy3t qojq ====").unwrap();
+        assert_eq!(secret.expose_secret(), b"1234567890");
+
+        assert!(decode_base32("GEZDGNB1").is_err());
+        assert!(decode_base32("GEZDGNBVG").is_err());
+    }
+
+    #[test]
+    fn uri() {
+        let totp = Totp::parse_uri(
+            "otpauth://totp/Example:me?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ\
+             &digits=8&period=60&algorithm=sha256",
+        )
+        .unwrap();
+
+        assert_eq!(totp.secret.expose_secret(), SHA1_SEED);
+        assert_eq!(totp.digits, 8);
+        assert_eq!(totp.period, 60);
+        assert_eq!(totp.algorithm, Algorithm::Sha256);
+
+        let totp = Totp::parse_uri("otpauth://totp/me?secret=GEZDGNBV").unwrap();
+        assert_eq!(totp.digits, Totp::DEFAULT_DIGITS);
+        assert_eq!(totp.period, Totp::DEFAULT_PERIOD);
+        assert_eq!(totp.algorithm, Algorithm::Sha1);
+    }
+
+    #[test]
+    fn invalid_uri() {
+        let invalid = [
+            "otpauth://hotp/me?secret=GEZDGNBV",
+            "otpauth://totp/me",
+            "otpauth://totp/me?secret=GEZDGNB1",
+            "otpauth://totp/me?secret=GEZDGNBV&digits=0",
+            "otpauth://totp/me?secret=GEZDGNBV&digits=11",
+            "otpauth://totp/me?secret=GEZDGNBV&digits=six",
+            "otpauth://totp/me?secret=GEZDGNBV&period=0",
+            "otpauth://totp/me?secret=GEZDGNBV&algorithm=md5",
+        ];
+
+        for uri in invalid {
+            assert!(Totp::parse_uri(uri).is_err(), "{uri}");
+        }
+    }
+}
@@ -0,0 +1,79 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use std::{fmt, io, str};
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use pimalaya_toolbox::terminal::printer::Printer;
+use secrecy::{ExposeSecret, SecretString};
+use serde::Serialize;
+
+use crate::{agent, config::Config, memory, rotation::State, store::StoreExt, totp::Totp};
+
+/// Write a TOTP seed into the store.
+///
+/// The seed can be either an otpauth:// URI or a base32 seed. It is
+/// validated before being stored. When no argument is provided, the
+/// seed is read from stdin.
+#[derive(Parser, Debug)]
+pub struct WriteTotpCommand {
+    /// Name of the store in the configuration file.
+    pub store: String,
+
+    /// The otpauth:// URI or the base32 seed.
+    pub seed: Option<SecretString>,
+}
+
+impl WriteTotpCommand {
+    pub fn execute(self, printer: &mut impl Printer, config: &Config) -> Result<()> {
+        let store = config.get_store(&self.store)?;
+
+        let seed = match self.seed {
+            Some(seed) => {
+                config.argv_secrets.check("SEED")?;
+                seed
+            }
+            None => {
+                let buf =
+                    memory::read_locked(io::stdin()).context("Cannot read seed from stdin")?;
+                let buf =
+                    str::from_utf8(buf.expose_secret()).context("Cannot read seed from stdin")?;
+                buf.trim().into()
+            }
+        };
+
+        Totp::parse(seed.expose_secret())?;
+        store.write(seed)?;
+        State::record_write(&self.store, &store);
+        agent::forget(&config.agent, &store);
+
+        printer.out(SeedWritten { store: self.store })
+    }
+}
+
+#[derive(Serialize)]
+struct SeedWritten {
+    store: String,
+}
+
+impl fmt::Display for SeedWritten {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TOTP seed successfully written to {}", self.store)
+    }
+}
@@ -0,0 +1,154 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! Exercises the asynchronous store operations without any async
+//! runtime, futures being driven by a minimal executor.
+
+#![cfg(any(
+    feature = "dbus-secret-service",
+    feature = "zbus-secret-service",
+    feature = "keyutils"
+))]
+
+use std::{
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake},
+    thread::{self, Thread},
+};
+
+use mimosa::store::{AsyncStoreExt, Store};
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Arc::new(ThreadWaker(thread::current())).into();
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+/// Runs the given ignored test of this binary in a child process
+/// with the given environment, leaving the environment of the other
+/// tests untouched.
+#[cfg(any(feature = "dbus-secret-service", feature = "zbus-secret-service"))]
+fn run_isolated(test: &str, vars: &[(&str, &str)]) {
+    let output = std::process::Command::new(std::env::current_exe().unwrap())
+        .args([test, "--exact", "--ignored"])
+        .envs(vars.iter().copied())
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "isolated test {test} failed:\n{stdout}"
+    );
+    assert!(
+        stdout.contains("1 passed"),
+        "isolated test {test} not run:\n{stdout}"
+    );
+}
+
+/// Loads the given store from a configuration file of its own.
+#[cfg(any(feature = "dbus-secret-service", feature = "zbus-secret-service"))]
+fn load_store(name: &str, toml: &str) -> Store {
+    use std::fs;
+
+    use mimosa::config::Config;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(format!("{name}.toml"));
+    fs::write(&path, toml).unwrap();
+
+    Config::load(&[path]).unwrap().get_store(name).unwrap()
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+#[cfg(any(feature = "dbus-secret-service", feature = "zbus-secret-service"))]
+fn unreachable_secret_service() {
+    let bus = ("DBUS_SESSION_BUS_ADDRESS", "unix:path=/nonexistent");
+    run_isolated("unreachable_secret_service_isolated", &[bus]);
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+#[cfg(any(feature = "dbus-secret-service", feature = "zbus-secret-service"))]
+#[ignore = "run by unreachable_secret_service, with its own session bus"]
+fn unreachable_secret_service_isolated() {
+    use mimosa::error::ErrorKind;
+
+    let store = load_store(
+        "unreachable",
+        r#"
+        [stores.unreachable]
+        store = "secret-service"
+        secret-service.service = "mimosa-test-async"
+        secret-service.user = "me"
+        "#,
+    );
+
+    let err = block_on(store.read()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::BackendUnavailable);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+#[cfg(feature = "keyutils")]
+#[ignore = "needs a session keyring"]
+fn keyutils_round_trip() {
+    use mimosa::store::StoreKind;
+    use secrecy::{ExposeSecret, SecretString};
+
+    let service = format!("mimosa-test-async-{}", std::process::id());
+
+    let store = Store::builder(service, "me")
+        .kind(StoreKind::LinuxKeyutils)
+        .history(1)
+        .build()
+        .unwrap();
+
+    block_on(async {
+        store.write(SecretString::from("first")).await.unwrap();
+        store.write(SecretString::from("second")).await.unwrap();
+
+        let secret = store.read().await.unwrap();
+        assert_eq!(secret.expose_secret(), "second");
+
+        let previous = store.read_history_async(1).await.unwrap().unwrap();
+        assert_eq!(previous.expose_secret(), b"first");
+
+        assert!(store.remove().await.unwrap());
+        assert!(!store.exists().await.unwrap());
+        assert!(store.read_history_async(1).await.unwrap().is_none());
+    });
+}
@@ -0,0 +1,187 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+//! Password strength scoring and store policies.
+
+use std::fs;
+
+use mimosa::audit::{Dictionary, Policy, Strength, WEAK_ENTROPY, WEAK_LENGTH};
+
+fn strength(password: &str) -> Strength {
+    Strength::new(password, &Dictionary::default())
+}
+
+fn policy(toml: &str) -> Policy {
+    toml::from_str(toml).unwrap()
+}
+
+#[test]
+fn strong() {
+    let strength = strength("Xk9#mQ2p-Lv7");
+
+    assert_eq!(strength.length, 12);
+    assert_eq!(strength.classes(), 4);
+    assert!(!strength.dictionary);
+    assert!(strength.weaknesses().is_empty());
+}
+
+#[test]
+fn length_threshold() {
+    let short = strength("Xk9#mQ2");
+    assert_eq!(short.length, WEAK_LENGTH - 1);
+    assert!(short.weaknesses()[0].starts_with("too short"));
+
+    let long = strength("Xk9#mQ2p");
+    assert_eq!(long.length, WEAK_LENGTH);
+    assert!(long.weaknesses().is_empty());
+
+    // characters are counted, not bytes
+    assert_eq!(strength("éééé").length, 4);
+}
+
+#[test]
+fn entropy_threshold() {
+    // 8 characters out of 62 is about 47.6 bits
+    let low = strength("Xk9mQ2pL");
+    assert!(low.entropy < WEAK_ENTROPY);
+    assert_eq!(
+        low.weaknesses(),
+        ["low entropy: 48 bits, expected at least 50"]
+    );
+
+    // 9 characters out of 62 is about 53.6 bits
+    let high = strength("Xk9mQ2pLv");
+    assert!(high.entropy >= WEAK_ENTROPY);
+    assert!(high.weaknesses().is_empty());
+
+    assert_eq!(strength("").entropy, 0.0);
+}
+
+#[test]
+fn character_classes() {
+    let strength = strength("xkqmzpvlwjrt");
+
+    assert!(strength.lowercase);
+    assert!(!strength.uppercase);
+    assert!(!strength.digits);
+    assert!(!strength.symbols);
+    assert_eq!(strength.weaknesses(), ["single character class"]);
+
+    assert!(self::strength("a b").symbols);
+    assert_eq!(self::strength("aB1!").classes(), 4);
+}
+
+#[test]
+fn dictionary() {
+    assert!(strength("myPassword42!").dictionary);
+
+    // common substitutions are normalized
+    let strength = strength("Xk9-P@$$w0rd!");
+    assert!(strength.dictionary);
+    assert_eq!(
+        strength.weaknesses(),
+        ["contains a common password or dictionary word"]
+    );
+}
+
+#[test]
+fn dictionary_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("words.txt");
+    fs::write(&path, "Zqxj\nvk\n").unwrap();
+
+    let mut dictionary = Dictionary::default();
+    dictionary.extend_from_file(&path).unwrap();
+
+    assert!(dictionary.matches("Xk9#ZQXJ-Lv7"));
+
+    // too short words are ignored
+    assert!(!dictionary.matches("Xk9#mQ2p-Lv7"));
+}
+
+#[test]
+fn empty_policy() {
+    assert!(policy("").check(&strength("a")).is_empty());
+}
+
+#[test]
+fn policy_length() {
+    let policy = policy("min-length = 10\nmax-length = 12");
+
+    assert_eq!(
+        policy.check(&strength("Xk9#mQ2p")),
+        ["policy requires at least 10 characters"]
+    );
+    assert!(policy.check(&strength("Xk9#mQ2p-L")).is_empty());
+    assert!(policy.check(&strength("Xk9#mQ2p-Lv7")).is_empty());
+    assert_eq!(
+        policy.check(&strength("Xk9#mQ2p-Lv7w")),
+        ["policy allows at most 12 characters"]
+    );
+}
+
+#[test]
+fn policy_entropy() {
+    let policy = policy("min-entropy = 70.0");
+
+    assert_eq!(
+        policy.check(&strength("Xk9#mQ2p-L")),
+        ["policy requires at least 70 bits of entropy"]
+    );
+    assert!(policy.check(&strength("Xk9#mQ2p-Lv7")).is_empty());
+}
+
+#[test]
+fn policy_classes() {
+    let policy = policy(
+        "require-lowercase = true\n\
+         require-uppercase = true\n\
+         require-digits = true\n\
+         require-symbols = true",
+    );
+
+    assert!(policy.check(&strength("Xk9#")).is_empty());
+    assert_eq!(
+        policy.check(&strength("XK9#")),
+        ["policy requires a lowercase letter"]
+    );
+    assert_eq!(
+        policy.check(&strength("xk9#")),
+        ["policy requires an uppercase letter"]
+    );
+    assert_eq!(policy.check(&strength("Xkq#")), ["policy requires a digit"]);
+    assert_eq!(
+        policy.check(&strength("Xk9q")),
+        ["policy requires a symbol"]
+    );
+}
+
+#[test]
+fn policy_dictionary() {
+    let policy = policy("forbid-dictionary = true");
+
+    assert_eq!(
+        policy.check(&strength("Xk9-P@$$w0rd!")),
+        ["policy forbids dictionary words"]
+    );
+    assert!(policy.check(&strength("Xk9#mQ2p-Lv7")).is_empty());
+}
+
+#[test]
+fn policy_unknown_rule() {
+    assert!(toml::from_str::<Policy>("min-digits = 2").is_err());
+}
@@ -0,0 +1,76 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! Loading of configuration files, and kinds of their errors.
+
+use std::{fs, path::PathBuf};
+
+use mimosa::{
+    config::Config,
+    error::{Error, ErrorKind},
+};
+use tempfile::TempDir;
+
+fn write_config(name: &str, content: &str) -> (TempDir, PathBuf) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(name);
+    fs::write(&path, content).unwrap();
+    (dir, path)
+}
+
+#[test]
+#[cfg(not(feature = "keyutils"))]
+fn feature_missing() {
+    let (_dir, path) = write_config(
+        "feature-missing.toml",
+        "[stores.example]\n\
+         store = \"linux-keyutils\"\n\
+         linux-keyutils.service = \"my-app\"\n\
+         linux-keyutils.user = \"me\"\n",
+    );
+
+    let err = Config::load(&[path]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::FeatureMissing);
+    assert_eq!(err.kind().exit_code(), 7);
+}
+
+#[test]
+fn invalid_store() {
+    let (_dir, path) = write_config(
+        "invalid-store.toml",
+        "[stores.example]\n\
+         store = \"secret-service\"\n\
+         secret-service.service = \"my-app\"\n",
+    );
+
+    let err = Config::load(&[path]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Config);
+}
+
+#[test]
+fn innermost_kind() {
+    let inner = Error::feature_missing("keyutils");
+    let outer = Error::config("Cannot load config").with_source(inner);
+    let err = anyhow::Error::from(outer).context("Cannot read password");
+
+    assert_eq!(ErrorKind::of(&err), ErrorKind::FeatureMissing);
+
+    let inner = std::io::Error::other("untyped");
+    let err = anyhow::Error::from(Error::config("Cannot load config").with_source(inner));
+    assert_eq!(ErrorKind::of(&err), ErrorKind::Config);
+}
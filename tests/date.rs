@@ -0,0 +1,88 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! Parsing and formatting of `YYYY-MM-DD` dates.
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use mimosa::date::{format, format_datetime, parse};
+
+#[test]
+fn epoch() {
+    assert_eq!(parse("1970-01-01").unwrap(), UNIX_EPOCH);
+    assert_eq!(format(UNIX_EPOCH), "1970-01-01");
+    assert_eq!(format_datetime(UNIX_EPOCH), "1970-01-01 00:00:00");
+}
+
+#[test]
+fn round_trip() {
+    for date in [
+        "2000-02-29",
+        "2024-02-29",
+        "2026-10-19",
+        "2100-12-31",
+        "9999-12-31",
+    ] {
+        assert_eq!(format(parse(date).unwrap()), date);
+    }
+}
+
+#[test]
+fn month_lengths() {
+    assert!(parse("2026-01-31").is_ok());
+    assert!(parse("2026-04-30").is_ok());
+    assert!(parse("2026-04-31").is_err());
+    assert!(parse("2026-06-31").is_err());
+    assert!(parse("2026-09-31").is_err());
+    assert!(parse("2026-11-31").is_err());
+    assert!(parse("2026-02-30").is_err());
+}
+
+#[test]
+fn leap_years() {
+    assert!(parse("2024-02-29").is_ok());
+    assert!(parse("2000-02-29").is_ok());
+    assert!(parse("2026-02-29").is_err());
+    assert!(parse("1900-02-29").is_err());
+    assert!(parse("2100-02-29").is_err());
+}
+
+#[test]
+fn out_of_range() {
+    assert!(parse("1969-12-31").is_err());
+    assert!(parse("10000-01-01").is_err());
+    assert!(parse("9223372036854775807-01-01").is_err());
+    assert!(parse("-1-01-01").is_err());
+    assert!(parse("2026-00-01").is_err());
+    assert!(parse("2026-13-01").is_err());
+    assert!(parse("2026-01-00").is_err());
+}
+
+#[test]
+fn invalid() {
+    assert!(parse("").is_err());
+    assert!(parse("2026-01").is_err());
+    assert!(parse("2026/01/01").is_err());
+    assert!(parse("2026-01-01-01").is_err());
+}
+
+#[test]
+fn datetime() {
+    let time = parse("2026-10-19").unwrap() + Duration::from_secs(13 * 3600 + 5 * 60 + 9);
+    assert_eq!(format_datetime(time), "2026-10-19 13:05:09");
+}
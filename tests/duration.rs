@@ -0,0 +1,56 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! Parsing and formatting of human-friendly durations.
+
+use std::time::Duration;
+
+use mimosa::duration::{format, parse};
+
+#[test]
+fn units() {
+    assert_eq!(parse("45").unwrap(), Duration::from_secs(45));
+    assert_eq!(parse("45s").unwrap(), Duration::from_secs(45));
+    assert_eq!(parse("5m").unwrap(), Duration::from_secs(300));
+    assert_eq!(parse("2h").unwrap(), Duration::from_secs(7200));
+    assert_eq!(parse(" 90 d ").unwrap(), Duration::from_secs(90 * 86400));
+}
+
+#[test]
+fn invalid() {
+    assert!(parse("").is_err());
+    assert!(parse("d").is_err());
+    assert!(parse("5w").is_err());
+    assert!(parse("-5m").is_err());
+}
+
+#[test]
+fn overflow() {
+    assert!(parse(&format!("{}d", u64::MAX / 86400 + 1)).is_err());
+    assert!(parse(&format!("{}s", u64::MAX)).is_ok());
+    assert!(parse("99999999999999999999").is_err());
+}
+
+#[test]
+fn round_trip() {
+    for duration in ["0s", "45s", "5m", "2h", "90d", "61s"] {
+        assert_eq!(format(parse(duration).unwrap()), duration);
+    }
+
+    assert_eq!(format(Duration::from_secs(3600 * 24 * 7)), "7d");
+}
@@ -0,0 +1,87 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+//! Encoding and decoding of binary secrets.
+
+use mimosa::encoding::Encoding;
+use secrecy::{ExposeSecret, SecretSlice};
+
+fn encode(encoding: Encoding, bytes: &[u8]) -> String {
+    let secret = SecretSlice::from(bytes.to_vec());
+    encoding.encode(&secret).expose_secret().to_owned()
+}
+
+fn decode(encoding: Encoding, input: &[u8]) -> Vec<u8> {
+    encoding.decode(input).unwrap().expose_secret().to_vec()
+}
+
+#[test]
+fn known_values() {
+    assert_eq!(encode(Encoding::Base64, b"secret"), "c2VjcmV0");
+    assert_eq!(encode(Encoding::Base64, b"secrets"), "c2VjcmV0cw==");
+    assert_eq!(encode(Encoding::Hex, b"\x00\xab\xff"), "00abff");
+    assert_eq!(encode(Encoding::Raw, b"secret"), "secret");
+
+    assert_eq!(decode(Encoding::Hex, b"00ABff"), b"\x00\xab\xff");
+}
+
+#[test]
+fn round_trip() {
+    let bytes: Vec<u8> = (0..=u8::MAX).collect();
+
+    for encoding in [Encoding::Base64, Encoding::Hex] {
+        let encoded = encode(encoding, &bytes);
+        assert_eq!(decode(encoding, encoded.as_bytes()), bytes, "{encoding:?}");
+    }
+
+    let encoded = encode(Encoding::Raw, "sécret\n".as_bytes());
+    assert_eq!(
+        decode(Encoding::Raw, encoded.as_bytes()),
+        "sécret\n".as_bytes()
+    );
+
+    for encoding in [Encoding::Raw, Encoding::Base64, Encoding::Hex] {
+        assert_eq!(encode(encoding, b""), "");
+        assert_eq!(decode(encoding, b""), b"");
+    }
+}
+
+#[test]
+fn raw_is_lossy() {
+    assert_eq!(encode(Encoding::Raw, b"\xffsecret"), "\u{fffd}secret");
+}
+
+#[test]
+fn whitespace() {
+    assert_eq!(decode(Encoding::Base64, b"c2VjcmV0\n"), b"secret");
+    assert_eq!(decode(Encoding::Base64, b" c2VjcmV0\r\n"), b"secret");
+    assert_eq!(decode(Encoding::Hex, b"\t736563726574\n\n"), b"secret");
+
+    // raw bytes are kept as-is
+    assert_eq!(decode(Encoding::Raw, b" secret\n"), b" secret\n");
+
+    // inner whitespaces are not part of textual encodings
+    assert!(Encoding::Base64.decode(b"c2Vj cmV0").is_err());
+    assert!(Encoding::Hex.decode(b"7365 6372").is_err());
+}
+
+#[test]
+fn invalid() {
+    assert!(Encoding::Base64.decode(b"c2VjcmV0c").is_err());
+    assert!(Encoding::Hex.decode(b"abc").is_err());
+    assert!(Encoding::Hex.decode(b"zz").is_err());
+}
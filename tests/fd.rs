@@ -0,0 +1,71 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! Reading and writing secrets through inherited file descriptors.
+
+#![cfg(unix)]
+
+use std::{
+    io::{Read, Seek, Write},
+    os::fd::AsRawFd,
+};
+
+use mimosa::password::source::{inherited_fd, read_fd};
+
+#[test]
+fn standard_streams() {
+    for fd in 0..=2 {
+        let err = inherited_fd(fd).unwrap_err();
+        assert!(err.to_string().contains("standard streams"));
+    }
+}
+
+#[test]
+fn invalid_fd() {
+    assert!(inherited_fd(-1).is_err());
+    assert!(inherited_fd(i32::MAX).is_err());
+}
+
+#[test]
+fn read_keeps_fd_open() {
+    let mut file = tempfile::tempfile().unwrap();
+    file.write_all(b"secret").unwrap();
+    file.rewind().unwrap();
+
+    let secret = read_fd(file.as_raw_fd()).unwrap();
+    assert_eq!(secret.expose_secret(), b"secret");
+
+    // the descriptor is still owned, and usable, by the file
+    file.write_all(b"!").unwrap();
+}
+
+#[test]
+fn write_keeps_fd_open() {
+    let mut file = tempfile::tempfile().unwrap();
+
+    let mut dup = inherited_fd(file.as_raw_fd()).unwrap();
+    dup.write_all(b"secret").unwrap();
+    drop(dup);
+
+    file.write_all(b"!").unwrap();
+    file.rewind().unwrap();
+
+    let mut content = String::new();
+    file.read_to_string(&mut content).unwrap();
+    assert_eq!(content, "secret!");
+}
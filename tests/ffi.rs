@@ -0,0 +1,93 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! Checks the C ABI against its header and its documented exit
+//! codes.
+
+#![cfg(feature = "ffi")]
+
+use std::{ffi::CString, fs, ptr};
+
+use mimosa::ffi::{mimosa_free_secret, mimosa_read, mimosa_remove, mimosa_write};
+
+const HEADER: &str = include_str!("../include/mimosa.h");
+const SOURCE: &str = include_str!("../src/ffi.rs");
+const PKG_CONFIG: &str = include_str!("../mimosa.pc.in");
+const INSTALL: &str = include_str!("../install-lib.sh");
+
+#[test]
+fn header_in_sync() {
+    let exported: Vec<&str> = SOURCE
+        .lines()
+        .filter_map(|line| line.strip_prefix("pub unsafe extern \"C\" fn "))
+        .filter_map(|line| line.split('(').next())
+        .collect();
+
+    assert_eq!(exported.len(), 4);
+
+    for name in exported {
+        assert!(
+            HEADER.contains(&format!(" {name}(")),
+            "`{name}` is missing from include/mimosa.h, regenerate it with cbindgen"
+        );
+    }
+}
+
+#[test]
+fn pkg_config_in_sync() {
+    for placeholder in ["@PREFIX@", "@VERSION@", "@LIBS_PRIVATE@"] {
+        assert!(
+            PKG_CONFIG.contains(placeholder),
+            "`{placeholder}` is missing from mimosa.pc.in"
+        );
+        assert!(
+            INSTALL.contains(&format!("s|{placeholder}|")),
+            "`{placeholder}` is not substituted by install-lib.sh"
+        );
+    }
+}
+
+#[test]
+fn unknown_store() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "").unwrap();
+
+    let config = CString::new(path.to_str().unwrap()).unwrap();
+    let store = CString::new("unknown").unwrap();
+
+    let mut secret = ptr::null_mut();
+    let mut len = 0;
+
+    unsafe {
+        let code = mimosa_read(config.as_ptr(), store.as_ptr(), &mut secret, &mut len);
+        assert_eq!(code, 3);
+        assert!(secret.is_null());
+
+        let code = mimosa_write(config.as_ptr(), store.as_ptr(), b"secret".as_ptr(), 6);
+        assert_eq!(code, 3);
+
+        let code = mimosa_remove(config.as_ptr(), store.as_ptr());
+        assert_eq!(code, 3);
+
+        let code = mimosa_read(config.as_ptr(), ptr::null(), &mut secret, &mut len);
+        assert_eq!(code, 1);
+
+        mimosa_free_secret(ptr::null_mut(), 0);
+    }
+}
@@ -0,0 +1,76 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! Keeps the history of a store consistent when writing and
+//! removing its secret.
+//!
+//! Runs against the Linux keyutils backend, which needs neither a
+//! daemon nor a session bus.
+
+#![cfg(all(target_os = "linux", feature = "keyutils"))]
+
+use mimosa::store::{Store, StoreExt, StoreKind};
+use secrecy::ExposeSecret;
+
+fn store(user: &str) -> Store {
+    Store::builder(
+        "mimosa-test-history",
+        format!("{user}-{}", std::process::id()),
+    )
+    .kind(StoreKind::LinuxKeyutils)
+    .history(2)
+    .build()
+    .unwrap()
+}
+
+fn previous(store: &Store, n: usize) -> Option<Vec<u8>> {
+    let secret = store.read_history(n).unwrap()?;
+    Some(secret.expose_secret().to_vec())
+}
+
+#[test]
+fn remove_purges_history() {
+    let store = store("purge");
+
+    for secret in ["a", "b", "c"] {
+        store.write(secret.into()).unwrap();
+    }
+
+    assert_eq!(previous(&store, 1).as_deref(), Some(&b"b"[..]));
+    assert_eq!(previous(&store, 2).as_deref(), Some(&b"a"[..]));
+
+    assert!(store.remove().unwrap());
+    assert!(!store.exists().unwrap());
+    assert_eq!(previous(&store, 1), None);
+    assert_eq!(previous(&store, 2), None);
+}
+
+#[test]
+fn remove_keeping_history() {
+    let store = store("keep");
+
+    for secret in ["a", "b"] {
+        store.write(secret.into()).unwrap();
+    }
+
+    assert!(store.backend().remove().unwrap());
+    assert_eq!(previous(&store, 1).as_deref(), Some(&b"a"[..]));
+
+    assert_eq!(store.remove_history().unwrap(), 1);
+    assert_eq!(previous(&store, 1), None);
+}
@@ -0,0 +1,162 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! Ensures secrets never reach logs, `Debug` representations or
+//! error reports, even with `--trace`.
+
+use std::{
+    error::Error as StdError,
+    fs,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use clap::Parser;
+use mimosa::{cli::Cli, encoding::Encoding, error::Error};
+use tempfile::TempDir;
+
+const SECRET: &str = "qu1ckbr0wnf0x";
+
+/// Writes a configuration whose only store cannot be reached, with
+/// the agent disabled so that a running one cannot answer instead.
+fn config() -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+
+    let config = "[stores.example]\n\
+        store = \"secret-service\"\n\
+        secret-service.service = \"mimosa-test\"\n\
+        secret-service.user = \"me\"\n\
+        \n\
+        [agent]\n\
+        disable = true\n";
+    fs::write(dir.path().join("config.toml"), config).unwrap();
+
+    dir
+}
+
+/// Runs the CLI with `--trace`, returning its stdout and stderr.
+fn run(args: &[&str], stdin: &str) -> String {
+    let dir = config();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mimosa"))
+        .arg("--config")
+        .arg(dir.path().join("config.toml"))
+        .arg("--trace")
+        .args(args)
+        .env("DBUS_SESSION_BUS_ADDRESS", "unix:path=/nonexistent")
+        .env("XDG_RUNTIME_DIR", dir.path())
+        .env("XDG_STATE_HOME", dir.path())
+        .env("MIMOSA_TEST_SECRET", SECRET)
+        .env_remove("RUST_LOG")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+
+    let mut out = String::from_utf8_lossy(&output.stdout).into_owned();
+    out.push_str(&String::from_utf8_lossy(&output.stderr));
+    out
+}
+
+#[test]
+fn cli_debug() {
+    let args = [
+        vec!["mimosa", "password", "write", "example", SECRET],
+        vec!["mimosa", "password", "read", "example", "--default", SECRET],
+        vec!["mimosa", "totp", "write", "example", SECRET],
+    ];
+
+    for args in args {
+        let cli = Cli::try_parse_from(&args).unwrap();
+        let debug = format!("{cli:?}");
+        assert!(!debug.contains(SECRET), "secret leaked in {debug}");
+    }
+}
+
+#[test]
+fn trace_output() {
+    let runs = [
+        run(&["password", "write", "example"], SECRET),
+        run(&["password", "write", "example", SECRET], ""),
+        run(
+            &[
+                "password",
+                "write",
+                "example",
+                "--from-env",
+                "MIMOSA_TEST_SECRET",
+            ],
+            "",
+        ),
+        run(&["--json", "password", "write", "example"], SECRET),
+        run(
+            &["password", "write", "example", "--encoding", "hex"],
+            SECRET,
+        ),
+        run(&["password", "read", "example", "--default", SECRET], ""),
+    ];
+
+    for output in runs {
+        assert!(!output.contains(SECRET), "secret leaked in {output}");
+    }
+}
+
+#[test]
+fn decode_errors() {
+    for encoding in [Encoding::Base64, Encoding::Hex] {
+        let err = encoding.decode(b"AA!!").unwrap_err();
+        let report = format!("{err:?} {err:#}");
+        let leaked = report.contains('!') || report.contains("33");
+        assert!(!leaked, "invalid symbol leaked in {report}");
+    }
+}
+
+#[test]
+fn keyring_errors() {
+    let secret = SECRET.as_bytes().to_vec();
+
+    let errors = [
+        keyring_core::Error::BadEncoding(secret.clone()),
+        keyring_core::Error::BadDataFormat(secret, "bad format".into()),
+    ];
+
+    for err in errors {
+        let err = Error::keyring("Cannot read secret from keyring", err);
+        let mut report = format!("{err:?}");
+        let mut source = StdError::source(&err);
+
+        while let Some(err) = source {
+            report.push_str(&format!(" {err:?}"));
+            source = err.source();
+        }
+
+        let leaked =
+            report.contains(SECRET) || report.contains(&format!("{:?}", SECRET.as_bytes()));
+        assert!(!leaked, "secret leaked in {report}");
+    }
+}
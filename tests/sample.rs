@@ -0,0 +1,47 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+//! Keeps `config.sample.toml` in sync with the configuration types.
+//!
+//! Run with `MIMOSA_BLESS=1` to regenerate the sample instead of
+//! checking it.
+
+use std::{env, fs};
+
+const SAMPLE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/config.sample.toml");
+
+#[test]
+fn sample_in_sync() {
+    let generated = mimosa::config::sample();
+
+    if env::var("MIMOSA_BLESS").is_ok() {
+        fs::write(SAMPLE, generated).unwrap();
+    } else {
+        let sample = fs::read_to_string(SAMPLE).unwrap();
+        assert_eq!(
+            sample, generated,
+            "config.sample.toml is out of sync, run with MIMOSA_BLESS=1"
+        );
+    }
+}
+
+#[cfg(any(feature = "dbus-secret-service", feature = "zbus-secret-service"))]
+#[test]
+fn sample_loads() {
+    let config = mimosa::config::Config::load(&[SAMPLE.into()]).unwrap();
+    assert!(config.stores.contains_key("example"));
+}
@@ -0,0 +1,185 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+//! Round-trip tests between stores and their configuration
+//! representation.
+
+#[cfg(any(
+    feature = "dbus-secret-service",
+    feature = "zbus-secret-service",
+    feature = "keyutils",
+    feature = "apple-keychain",
+    feature = "windows-credential-manager"
+))]
+use mimosa::store::Store;
+use mimosa::store::StoreKind;
+
+#[cfg(any(
+    feature = "dbus-secret-service",
+    feature = "zbus-secret-service",
+    feature = "keyutils",
+    feature = "apple-keychain",
+    feature = "windows-credential-manager"
+))]
+fn round_trip(toml: &str) -> String {
+    let store: Store = toml::from_str(toml).unwrap();
+    let serialized = toml::to_string(&store).unwrap();
+
+    let store: Store = toml::from_str(&serialized).unwrap();
+    let reserialized = toml::to_string(&store).unwrap();
+    assert_eq!(serialized, reserialized);
+
+    serialized
+}
+
+#[cfg(any(feature = "dbus-secret-service", feature = "zbus-secret-service"))]
+#[test]
+fn secret_service() {
+    let toml = r#"
+        store = "secret-service"
+        secret-service.service = "my-app"
+        secret-service.user = "me"
+        rotate-every = "30d"
+        expires = "2030-01-01"
+        history = 3
+        policy.min-length = 16
+    "#;
+
+    let serialized = round_trip(toml);
+    assert!(serialized.contains(r#"store = "secret-service""#));
+    assert!(serialized.contains(r#"rotate-every = "30d""#));
+    assert!(serialized.contains(r#"expires = "2030-01-01""#));
+    assert!(serialized.contains("history = 3"));
+    assert!(serialized.contains("min-length = 16"));
+}
+
+#[cfg(all(
+    any(target_os = "linux", target_os = "freebsd"),
+    any(feature = "dbus-secret-service", feature = "zbus-secret-service")
+))]
+#[test]
+fn detected() {
+    let toml = r#"
+        secret-service.service = "my-app"
+        secret-service.user = "me"
+    "#;
+
+    let store: Store = toml::from_str(toml).unwrap();
+    assert!(store.is_detected());
+
+    let serialized = round_trip(toml);
+    assert!(!serialized.contains("store ="));
+}
+
+#[cfg(feature = "keyutils")]
+#[test]
+fn keyutils_alias() {
+    let toml = r#"
+        store = "keyutils"
+        keyutils.service = "my-app"
+        keyutils.user = "me"
+    "#;
+
+    let serialized = round_trip(toml);
+    assert!(serialized.contains(r#"store = "linux-keyutils""#));
+    assert!(serialized.contains("[linux-keyutils]"));
+}
+
+#[cfg(feature = "apple-keychain")]
+#[test]
+fn apple_keychain_alias() {
+    let toml = r#"
+        store = "apple-keychain"
+        apple-keychain.service = "my-app"
+        apple-keychain.user = "me"
+    "#;
+
+    let serialized = round_trip(toml);
+    assert!(serialized.contains(r#"store = "apple-native""#));
+    assert!(serialized.contains("[apple-native]"));
+}
+
+#[cfg(feature = "windows-credential-manager")]
+#[test]
+fn windows_credential_manager_alias() {
+    let toml = r#"
+        store = "windows-credential-manager"
+        windows-credential-manager.service = "my-app"
+        windows-credential-manager.user = "me"
+    "#;
+
+    let serialized = round_trip(toml);
+    assert!(serialized.contains(r#"store = "windows-native""#));
+    assert!(serialized.contains("[windows-native]"));
+}
+
+#[cfg(feature = "keyutils")]
+#[test]
+fn totp_entry() {
+    use mimosa::store::EntryType;
+
+    let toml = r#"
+        keyutils.service = "my-app"
+        keyutils.user = "me"
+        entry = "totp"
+    "#;
+
+    let store: Store = toml::from_str(toml).unwrap();
+    assert_eq!(store.entry(), EntryType::Totp);
+
+    let serialized = round_trip(toml);
+    assert!(serialized.contains(r#"entry = "totp""#));
+
+    // passwords are the default, and are not serialized
+    let serialized = round_trip(&toml.replace("totp", "password"));
+    assert!(!serialized.contains("entry"));
+
+    let toml = toml.replace("totp", "hotp");
+    assert!(toml::from_str::<Store>(&toml).is_err());
+}
+
+#[test]
+fn kind_names() {
+    for kind in StoreKind::ALL {
+        let names = [Some(kind.as_str()), kind.alias()];
+
+        for name in names.into_iter().flatten() {
+            let toml = format!("kind = {name:?}");
+            let parsed: toml::Table = toml::from_str(&toml).unwrap();
+            let parsed: StoreKind = parsed["kind"].clone().try_into().unwrap();
+            assert_eq!(parsed.as_str(), kind.as_str());
+        }
+    }
+}
+
+#[cfg(any(feature = "dbus-secret-service", feature = "zbus-secret-service"))]
+#[test]
+fn builder() {
+    let store = Store::builder("my-app", "me")
+        .kind(StoreKind::SecretService)
+        .history(2)
+        .build()
+        .unwrap();
+
+    assert!(!store.is_detected());
+    assert_eq!(store.backend().kind(), StoreKind::SecretService);
+    assert_eq!(store.backend().service(), "my-app");
+
+    let serialized = toml::to_string(&store).unwrap();
+    assert!(serialized.contains(r#"store = "secret-service""#));
+    assert!(serialized.contains("history = 2"));
+}
@@ -0,0 +1,78 @@
+// This file is part of Mimosa, a CLI to manage passwords.
+//
+// Copyright (C) 2026 Clément DOUIN <pimalaya.org@posteo.net>
+//
+// This program is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Affero General Public License
+// as published by the Free Software Foundation, either version 3 of
+// the License, or (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public
+// License along with this program. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! Rendering of store templates.
+
+use mimosa::template::{render, var_parser, Vars};
+
+fn vars(vars: &[(&str, &str)]) -> Vars {
+    vars.iter()
+        .map(|(key, val)| (key.to_string(), val.to_string()))
+        .collect()
+}
+
+#[test]
+fn placeholders() {
+    let vars = vars(&[("user", "me"), ("host", "example.org")]);
+
+    assert_eq!(render("{user}@{host}", &vars).unwrap(), "me@example.org");
+    assert_eq!(render("{ user }", &vars).unwrap(), "me");
+    assert_eq!(render("no placeholder", &vars).unwrap(), "no placeholder");
+    assert_eq!(render("", &vars).unwrap(), "");
+}
+
+#[test]
+fn escaped_braces() {
+    let vars = vars(&[("user", "me")]);
+
+    assert_eq!(render("{{user}}", &vars).unwrap(), "{user}");
+    assert_eq!(render("{{{user}}}", &vars).unwrap(), "{me}");
+}
+
+#[test]
+fn env_fallback() {
+    // cargo sets this variable when running tests
+    let name = env!("CARGO_PKG_NAME");
+
+    let empty = Vars::new();
+    assert_eq!(render("{CARGO_PKG_NAME}", &empty).unwrap(), name);
+
+    let vars = vars(&[("CARGO_PKG_NAME", "overridden")]);
+    assert_eq!(render("{CARGO_PKG_NAME}", &vars).unwrap(), "overridden");
+}
+
+#[test]
+fn missing_variable() {
+    let err = render("{mimosa-test-missing}", &Vars::new()).unwrap_err();
+    assert!(err.to_string().contains("mimosa-test-missing"));
+}
+
+#[test]
+fn unclosed_placeholder() {
+    let vars = vars(&[("user", "me")]);
+    assert!(render("{user", &vars).is_err());
+}
+
+#[test]
+fn var_pairs() {
+    assert_eq!(var_parser("user=me"), Ok(("user".into(), "me".into())));
+    assert_eq!(var_parser(" user =a=b"), Ok(("user".into(), "a=b".into())));
+    assert_eq!(var_parser("user="), Ok(("user".into(), "".into())));
+    assert!(var_parser("user").is_err());
+    assert!(var_parser("=me").is_err());
+}